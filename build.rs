@@ -129,7 +129,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 		.type_attribute("GetBlockWithDetailsByNumberRequest", SERDE_ANNOTATION)
 		.type_attribute("GetBlockWithDetailsByNumberResponse", SERDE_ANNOTATION)
 		.protoc_arg("--experimental_allow_proto3_optional");
-	tonic_build::configure().compile_with_config(config, &["l1x_rpc_model.proto"], &["proto"])?;
+	// `NodeClient::connect` needs tonic's transport, which does not build for wasm32.
+	tonic_build::configure()
+		.build_transport(std::env::var_os("CARGO_FEATURE_GRPC").is_some())
+		.compile_with_config(config, &["l1x_rpc_model.proto"], &["proto"])?;
 
 	Ok(())
 }
@@ -0,0 +1,188 @@
+//! Typed 20-byte L1X account and contract addresses.
+
+use crate::primitives;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use sha3::{Digest, Keccak256};
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AddressError {
+	#[error("invalid length {len} for field `{field}`, expected 20 bytes")]
+	InvalidLength { field: &'static str, len: usize },
+	#[error("invalid hex in field `{field}`: {reason}")]
+	InvalidHex { field: &'static str, reason: String },
+	#[error("invalid EIP-55 checksum in field `{field}`")]
+	InvalidChecksum { field: &'static str },
+}
+
+/// A 20-byte address.
+///
+/// `Display` and `From<Address> for String` produce lowercase hex without a `0x` prefix, the
+/// format the node expects in string request fields. [`Address::to_checksum`] produces the
+/// `0x`-prefixed EIP-55 form.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Address(pub primitives::Address);
+
+impl Address {
+	pub const LEN: usize = 20;
+
+	pub fn as_bytes(&self) -> &primitives::Address {
+		&self.0
+	}
+
+	/// Converts the bytes of the proto field `field`, naming it in the error.
+	pub fn from_field(bytes: &[u8], field: &'static str) -> Result<Self, AddressError> {
+		bytes
+			.try_into()
+			.map(Self)
+			.map_err(|_| AddressError::InvalidLength { field, len: bytes.len() })
+	}
+
+	/// Parses the hex string of the proto field `field`, naming it in the error.
+	///
+	/// A `0x` prefix is optional. Mixed-case input must carry a valid EIP-55 checksum.
+	pub fn parse_field(s: &str, field: &'static str) -> Result<Self, AddressError> {
+		let hex_str = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+		let bytes = hex::decode(hex_str)
+			.map_err(|e| AddressError::InvalidHex { field, reason: e.to_string() })?;
+		let address = Self::from_field(&bytes, field)?;
+
+		let has_lower = hex_str.chars().any(|c| c.is_ascii_lowercase());
+		let has_upper = hex_str.chars().any(|c| c.is_ascii_uppercase());
+		if has_lower && has_upper && address.to_checksum()[2..] != *hex_str {
+			return Err(AddressError::InvalidChecksum { field })
+		}
+		Ok(address)
+	}
+
+	/// `0x`-prefixed EIP-55 mixed-case checksum encoding.
+	pub fn to_checksum(&self) -> String {
+		let lower = hex::encode(self.0);
+		let hash = Keccak256::digest(lower.as_bytes());
+		let checksummed: String = lower
+			.chars()
+			.enumerate()
+			.map(|(i, c)| {
+				let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+				if nibble >= 8 {
+					c.to_ascii_uppercase()
+				} else {
+					c
+				}
+			})
+			.collect();
+		format!("0x{}", checksummed)
+	}
+}
+
+impl fmt::Display for Address {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", hex::encode(self.0))
+	}
+}
+
+impl fmt::Debug for Address {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "Address({})", self.to_checksum())
+	}
+}
+
+impl FromStr for Address {
+	type Err = AddressError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::parse_field(s, "address")
+	}
+}
+
+impl From<primitives::Address> for Address {
+	fn from(value: primitives::Address) -> Self {
+		Self(value)
+	}
+}
+
+impl From<Address> for primitives::Address {
+	fn from(value: Address) -> Self {
+		value.0
+	}
+}
+
+impl TryFrom<&[u8]> for Address {
+	type Error = AddressError;
+
+	fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+		Self::from_field(value, "address")
+	}
+}
+
+impl TryFrom<Vec<u8>> for Address {
+	type Error = AddressError;
+
+	fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+		Self::from_field(&value, "address")
+	}
+}
+
+impl From<Address> for Vec<u8> {
+	fn from(value: Address) -> Self {
+		value.0.to_vec()
+	}
+}
+
+impl From<Address> for String {
+	fn from(value: Address) -> Self {
+		value.to_string()
+	}
+}
+
+impl AsRef<[u8]> for Address {
+	fn as_ref(&self) -> &[u8] {
+		&self.0
+	}
+}
+
+impl Serialize for Address {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		if serializer.is_human_readable() {
+			serializer.serialize_str(&self.to_string())
+		} else {
+			serializer.serialize_bytes(&self.0)
+		}
+	}
+}
+
+struct AddressVisitor;
+
+impl<'de> de::Visitor<'de> for AddressVisitor {
+	type Value = Address;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "a hex string or 20 bytes")
+	}
+
+	fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+		v.parse().map_err(E::custom)
+	}
+
+	fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+		Address::try_from(v).map_err(E::custom)
+	}
+
+	fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+		let mut bytes = Vec::with_capacity(Address::LEN);
+		while let Some(byte) = seq.next_element::<u8>()? {
+			bytes.push(byte);
+		}
+		Address::try_from(bytes).map_err(de::Error::custom)
+	}
+}
+
+impl<'de> Deserialize<'de> for Address {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		if deserializer.is_human_readable() {
+			deserializer.deserialize_any(AddressVisitor)
+		} else {
+			deserializer.deserialize_bytes(AddressVisitor)
+		}
+	}
+}
@@ -0,0 +1,350 @@
+//! Fluent construction of signed `SubmitTransactionRequestV2`s.
+//!
+//! ```ignore
+//! let request = TransactionBuilder::new()
+//!     .call(contract, "transfer", args)
+//!     .deposit(10)
+//!     .sign_with_grpc(&signer, &mut client)
+//!     .await?;
+//! client.submit_transaction_v2(request).await?;
+//! ```
+//!
+//! The returned request serializes to the shape the JSON-RPC `l1x_submitTransactionV2` method
+//! expects, so the same request can be sent over either transport.
+//!
+//! [`TransactionBuilder::prepare_with_grpc`] stops short of signing and returns an
+//! [`OfflineTransaction`] to be signed on another machine.
+
+use crate::{
+	address::Address,
+	fee::FeePolicy,
+	json,
+	offline::OfflineTransaction,
+	primitives::*,
+	rpc_model::{
+		submit_transaction_request_v2::TransactionType, ConversionError, CreateStakingPool,
+		NativeTokenTransfer, SmartContractDeploymentV2, SmartContractFunctionCallV2,
+		SmartContractInitV2, Stake, SubmitTransactionRequestV2, UnStake,
+	},
+	signer::Signer,
+	transaction::{AccessType, ContractType, TransactionTypeV2},
+};
+use anyhow::{anyhow, Context, Result};
+use reqwest::RequestBuilder;
+#[cfg(feature = "grpc")]
+use {
+	crate::rpc_model::{node_client::NodeClient, GetAccountStateRequest},
+	tonic::transport::Channel,
+};
+
+/// Builds and signs a V2 transaction, filling in the nonce and fee limit from a node when they
+/// are not set explicitly. The fee limit is the node's estimate unless another [`FeePolicy`] is
+/// chosen.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionBuilder {
+	transaction_type: Option<TransactionType>,
+	nonce: Option<Nonce>,
+	fee_policy: FeePolicy,
+	misuse: Option<String>,
+}
+
+impl TransactionBuilder {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Transfers `amount` native tokens to `to`.
+	pub fn transfer(self, to: Address, amount: Balance) -> Self {
+		self.with_type(TransactionType::NativeTokenTransfer(NativeTokenTransfer {
+			address: to.as_bytes().to_vec(),
+			amount: amount.to_string(),
+		}))
+	}
+
+	/// Deploys `code` as a public L1X VM contract with no deposit and an empty salt.
+	pub fn deploy(self, code: impl Into<ContractCode>) -> Self {
+		self.with_type(TransactionType::SmartContractDeployment(SmartContractDeploymentV2 {
+			access_type: AccessType::PUBLIC as i32,
+			contract_type: ContractType::L1XVM as i32,
+			contract_code: code.into(),
+			deposit: 0.to_string(),
+			salt: Vec::new(),
+		}))
+	}
+
+	/// Instantiates the contract code deployed at `code_address` with `arguments`.
+	pub fn init(self, code_address: Address, arguments: impl Into<ContractArgument>) -> Self {
+		self.with_type(TransactionType::SmartContractInit(SmartContractInitV2 {
+			contract_code_address: code_address.as_bytes().to_vec(),
+			arguments: arguments.into(),
+			deposit: 0.to_string(),
+		}))
+	}
+
+	/// Calls `function` on the contract instance at `contract` with `arguments`.
+	pub fn call(
+		self,
+		contract: Address,
+		function: impl Into<ContractFunction>,
+		arguments: impl Into<ContractArgument>,
+	) -> Self {
+		self.with_type(TransactionType::SmartContractFunctionCall(SmartContractFunctionCallV2 {
+			contract_instance_address: contract.as_bytes().to_vec(),
+			function_name: function.into(),
+			arguments: arguments.into(),
+			deposit: 0.to_string(),
+		}))
+	}
+
+	/// Creates a staking pool, leaving every parameter unset.
+	pub fn create_staking_pool(self) -> Self {
+		self.with_type(TransactionType::CreateStakingPool(CreateStakingPool::default()))
+	}
+
+	/// Stakes `amount` in the pool at `pool`.
+	pub fn stake(self, pool: Address, amount: Balance) -> Self {
+		self.with_type(TransactionType::Stake(Stake {
+			pool_address: pool.as_bytes().to_vec(),
+			amount: amount.to_string(),
+		}))
+	}
+
+	/// Withdraws `amount` from the pool at `pool`.
+	pub fn unstake(self, pool: Address, amount: Balance) -> Self {
+		self.with_type(TransactionType::Unstake(UnStake {
+			pool_address: pool.as_bytes().to_vec(),
+			amount: amount.to_string(),
+		}))
+	}
+
+	/// Sets the deposit of a deployment, initialization or function call.
+	pub fn deposit(mut self, deposit: Balance) -> Self {
+		match &mut self.transaction_type {
+			Some(TransactionType::SmartContractDeployment(v)) => v.deposit = deposit.to_string(),
+			Some(TransactionType::SmartContractInit(v)) => v.deposit = deposit.to_string(),
+			Some(TransactionType::SmartContractFunctionCall(v)) => v.deposit = deposit.to_string(),
+			_ => return self.misuse("deposit"),
+		}
+		self
+	}
+
+	/// Sets the salt of a deployment.
+	pub fn salt(mut self, salt: impl Into<Salt>) -> Self {
+		match &mut self.transaction_type {
+			Some(TransactionType::SmartContractDeployment(v)) => v.salt = salt.into(),
+			_ => return self.misuse("salt"),
+		}
+		self
+	}
+
+	/// Sets the access type of a deployment.
+	pub fn access_type(mut self, access_type: AccessType) -> Self {
+		match &mut self.transaction_type {
+			Some(TransactionType::SmartContractDeployment(v)) => v.access_type = access_type as i32,
+			_ => return self.misuse("access_type"),
+		}
+		self
+	}
+
+	/// Sets the contract type of a deployment.
+	pub fn contract_type(mut self, contract_type: ContractType) -> Self {
+		match &mut self.transaction_type {
+			Some(TransactionType::SmartContractDeployment(v)) =>
+				v.contract_type = contract_type as i32,
+			_ => return self.misuse("contract_type"),
+		}
+		self
+	}
+
+	/// Sets the parameters of a staking pool creation.
+	pub fn pool_config(mut self, config: CreateStakingPool) -> Self {
+		match &mut self.transaction_type {
+			Some(TransactionType::CreateStakingPool(v)) => *v = config,
+			_ => return self.misuse("pool_config"),
+		}
+		self
+	}
+
+	/// Uses `nonce` instead of fetching the account's next nonce.
+	pub fn nonce(mut self, nonce: Nonce) -> Self {
+		self.nonce = Some(nonce);
+		self
+	}
+
+	/// Uses `fee_limit` instead of the node's fee estimate.
+	pub fn fee_limit(self, fee_limit: Balance) -> Self {
+		self.fee_policy(FeePolicy::Fixed(fee_limit))
+	}
+
+	/// Chooses the fee limit from the node's fee estimate with `policy`.
+	pub fn fee_policy(mut self, policy: FeePolicy) -> Self {
+		self.fee_policy = policy;
+		self
+	}
+
+	pub fn transaction_type(&self) -> Option<&TransactionType> {
+		self.transaction_type.as_ref()
+	}
+
+	fn with_type(mut self, transaction_type: TransactionType) -> Self {
+		if self.transaction_type.is_some() {
+			return self.misuse("a second transaction")
+		}
+		self.transaction_type = Some(transaction_type);
+		self
+	}
+
+	fn misuse(mut self, what: &str) -> Self {
+		if self.misuse.is_none() {
+			let target = match &self.transaction_type {
+				Some(transaction_type) => type_name(transaction_type),
+				None => "no transaction",
+			};
+			self.misuse = Some(format!("{} cannot be applied to {}", what, target));
+		}
+		self
+	}
+
+	fn checked_type(&self) -> Result<&TransactionType> {
+		if let Some(misuse) = &self.misuse {
+			return Err(anyhow!("Invalid transaction builder: {}", misuse))
+		}
+		self.transaction_type.as_ref().ok_or(anyhow!("No transaction to build"))
+	}
+
+	/// Signs the transaction with an explicitly set nonce and fee limit.
+	pub async fn sign(self, signer: &dyn Signer) -> Result<SubmitTransactionRequestV2> {
+		let transaction_type = self.checked_type()?.clone();
+		let nonce = self.nonce.ok_or(anyhow!("Nonce is not set"))?;
+		let fee_limit = self.fee_policy.fixed().ok_or(anyhow!("Fee limit is not set"))?;
+
+		let signature = crate::sign_v2(signer, transaction_type.clone(), fee_limit, nonce).await?;
+		Ok(SubmitTransactionRequestV2 {
+			nonce: nonce.to_string(),
+			transaction_type: Some(transaction_type),
+			fee_limit: fee_limit.to_string(),
+			signature,
+			verifying_key: signer.verifying_key().await?,
+		})
+	}
+
+	/// Signs the transaction, fetching the nonce and fee estimate that are not set over gRPC.
+	#[cfg(feature = "grpc")]
+	pub async fn sign_with_grpc(
+		self,
+		signer: &dyn Signer,
+		client: &mut NodeClient<Channel>,
+	) -> Result<SubmitTransactionRequestV2> {
+		let verifying_key = signer.verifying_key().await?;
+		self.fill_with_grpc(&verifying_key, client).await?.sign(signer).await
+	}
+
+	/// Signs the transaction, fetching the nonce and fee estimate that are not set over
+	/// JSON-RPC. `client` must be cloneable, i.e. not carry a streaming body.
+	pub async fn sign_with_json_rpc(
+		self,
+		signer: &dyn Signer,
+		client: RequestBuilder,
+	) -> Result<SubmitTransactionRequestV2> {
+		let verifying_key = signer.verifying_key().await?;
+		self.fill_with_json_rpc(&verifying_key, client).await?.sign(signer).await
+	}
+
+	/// The unsigned transaction for the account of `verifying_key`, to be signed offline, with
+	/// an explicitly set nonce and fee limit.
+	pub fn prepare(self, verifying_key: VerifyingKeyBytes) -> Result<OfflineTransaction> {
+		let transaction_type = self.checked_type()?.clone();
+		let nonce = self.nonce.ok_or(anyhow!("Nonce is not set"))?;
+		let fee_limit = self.fee_policy.fixed().ok_or(anyhow!("Fee limit is not set"))?;
+		Ok(OfflineTransaction::new(transaction_type.try_into()?, nonce, fee_limit, verifying_key)?)
+	}
+
+	/// Like [`TransactionBuilder::prepare`], fetching the nonce and fee estimate that are not
+	/// set over gRPC.
+	#[cfg(feature = "grpc")]
+	pub async fn prepare_with_grpc(
+		self,
+		verifying_key: VerifyingKeyBytes,
+		client: &mut NodeClient<Channel>,
+	) -> Result<OfflineTransaction> {
+		self.fill_with_grpc(&verifying_key, client).await?.prepare(verifying_key)
+	}
+
+	/// Like [`TransactionBuilder::prepare`], fetching the nonce and fee estimate that are not
+	/// set over JSON-RPC.
+	pub async fn prepare_with_json_rpc(
+		self,
+		verifying_key: VerifyingKeyBytes,
+		client: RequestBuilder,
+	) -> Result<OfflineTransaction> {
+		self.fill_with_json_rpc(&verifying_key, client).await?.prepare(verifying_key)
+	}
+
+	#[cfg(feature = "grpc")]
+	async fn fill_with_grpc(
+		mut self,
+		verifying_key: &[u8],
+		client: &mut NodeClient<Channel>,
+	) -> Result<Self> {
+		let transaction_type = self.checked_type()?.clone();
+		if self.nonce.is_none() {
+			let address = crate::get_address_from_verifying_key(verifying_key)?;
+			let response =
+				client.get_account_state(GetAccountStateRequest { address }).await?.into_inner();
+			let account_state = response.account_state.ok_or(anyhow!("no account state"))?;
+			self.nonce = Some(next_nonce(&account_state.nonce)?);
+		}
+		let fee_limit = self
+			.fee_policy
+			.fee_limit_grpc(client, transaction_type, verifying_key.to_vec())
+			.await?;
+		Ok(self.fee_limit(fee_limit))
+	}
+
+	async fn fill_with_json_rpc(
+		mut self,
+		verifying_key: &[u8],
+		client: RequestBuilder,
+	) -> Result<Self> {
+		let transaction_type = self.checked_type()?.clone();
+		if self.nonce.is_none() {
+			let request = client.try_clone().context("JSON-RPC request is not cloneable")?;
+			let address = crate::get_address_from_verifying_key(verifying_key)?;
+			let nonce = json::get_account_nonce(request, address).await?;
+			self.nonce = Some(nonce.checked_add(1).ok_or(anyhow!("Nonce overflow"))?);
+		}
+		let fee_limit = self
+			.fee_policy
+			.fee_limit_json_rpc(client, transaction_type, verifying_key.to_vec())
+			.await?;
+		Ok(self.fee_limit(fee_limit))
+	}
+}
+
+/// Starts from a domain transaction, e.g. one decoded from a node response.
+impl TryFrom<TransactionTypeV2> for TransactionBuilder {
+	type Error = ConversionError;
+
+	fn try_from(transaction_type: TransactionTypeV2) -> Result<Self, Self::Error> {
+		Ok(Self { transaction_type: Some(transaction_type.try_into()?), ..Self::default() })
+	}
+}
+
+/// The nonce of the account's next transaction, one past its current nonce.
+#[cfg(feature = "grpc")]
+fn next_nonce(current: &str) -> Result<Nonce> {
+	let nonce: Nonce = current.parse().context("failed to parse nonce")?;
+	nonce.checked_add(1).ok_or(anyhow!("Nonce overflow"))
+}
+
+fn type_name(transaction_type: &TransactionType) -> &'static str {
+	match transaction_type {
+		TransactionType::NativeTokenTransfer(_) => "a native token transfer",
+		TransactionType::SmartContractDeployment(_) => "a smart contract deployment",
+		TransactionType::SmartContractInit(_) => "a smart contract init",
+		TransactionType::SmartContractFunctionCall(_) => "a smart contract function call",
+		TransactionType::CreateStakingPool(_) => "a staking pool creation",
+		TransactionType::Stake(_) => "a stake",
+		TransactionType::Unstake(_) => "an unstake",
+	}
+}
@@ -0,0 +1,263 @@
+//! Canonical signing bytes of [`TXSignPayload`] and [`TXSignPayloadV2`].
+//!
+//! The signed message is the SHA-256 digest of the bytes produced here. They are compact JSON
+//! (no whitespace) laid out exactly as follows, so changes to the serde derives of the payload
+//! types cannot silently change what clients sign:
+//!
+//! * a payload is the object `{"nonce":N,"transaction_type":T,"fee_limit":F}`, where in V1 `N` and
+//!   `F` are decimal numbers and in V2 they are decimal strings
+//! * a transaction type is the single-key object `{"<Variant>":V}` with the variant name as it
+//!   appears in `TransactionType` / `TransactionTypeV2SignPayload`. Tuple variants encode `V` as an
+//!   array of their fields, struct variants as an object with fields in declaration order
+//! * addresses and byte strings are arrays of decimal byte values, e.g. `[0,255]`
+//! * V1 balances and block numbers are decimal numbers. V2 `deposit` and `NativeTokenTransfer`
+//!   amounts are decimal strings, other V2 balances stay decimal numbers
+//! * absent optional values are `null`
+//! * `access_type` and `contract_type` are the quoted enum variant names, e.g. `"PUBLIC"`
+//! * strings use JSON escaping
+
+use crate::{
+	transaction::{AccessType, ContractType, TransactionType},
+	TXSignPayload, TXSignPayloadV2, TransactionTypeV2SignPayload,
+};
+
+fn bytes(value: &[u8]) -> String {
+	let items: Vec<String> = value.iter().map(|b| b.to_string()).collect();
+	format!("[{}]", items.join(","))
+}
+
+fn string(value: &str) -> String {
+	serde_json::Value::String(value.to_owned()).to_string()
+}
+
+fn optional<T>(value: &Option<T>, encode: impl Fn(&T) -> String) -> String {
+	value.as_ref().map(encode).unwrap_or_else(|| "null".to_owned())
+}
+
+fn object(fields: &[(&str, String)]) -> String {
+	let fields: Vec<String> = fields
+		.iter()
+		.map(|(name, value)| format!("{}:{}", string(name), value))
+		.collect();
+	format!("{{{}}}", fields.join(","))
+}
+
+fn array(items: &[String]) -> String {
+	format!("[{}]", items.join(","))
+}
+
+fn variant(name: &str, value: String) -> String {
+	object(&[(name, value)])
+}
+
+fn access_type(value: &AccessType) -> String {
+	string(match value {
+		AccessType::PRIVATE => "PRIVATE",
+		AccessType::PUBLIC => "PUBLIC",
+		AccessType::RESTICTED => "RESTICTED",
+	})
+}
+
+fn contract_type(value: &ContractType) -> String {
+	string(match value {
+		ContractType::L1XVM => "L1XVM",
+		ContractType::EVM => "EVM",
+		ContractType::XTALK => "XTALK",
+	})
+}
+
+fn staking_pool(
+	contract_instance_address: &Option<crate::Address>,
+	min_stake: &Option<crate::Balance>,
+	max_stake: &Option<crate::Balance>,
+	min_pool_balance: &Option<crate::Balance>,
+	max_pool_balance: &Option<crate::Balance>,
+	staking_period: &Option<crate::BlockNumber>,
+) -> String {
+	variant(
+		"CreateStakingPool",
+		object(&[
+			("contract_instance_address", optional(contract_instance_address, |a| bytes(a))),
+			("min_stake", optional(min_stake, u128::to_string)),
+			("max_stake", optional(max_stake, u128::to_string)),
+			("min_pool_balance", optional(min_pool_balance, u128::to_string)),
+			("max_pool_balance", optional(max_pool_balance, u128::to_string)),
+			("staking_period", optional(staking_period, u128::to_string)),
+		]),
+	)
+}
+
+fn transaction_type(value: &TransactionType) -> String {
+	match value {
+		TransactionType::NativeTokenTransfer(address, amount) =>
+			variant("NativeTokenTransfer", array(&[bytes(address), amount.to_string()])),
+		TransactionType::SmartContractDeployment {
+			access_type: access,
+			contract_type: contract,
+			contract_code,
+			value,
+			salt,
+		} => variant(
+			"SmartContractDeployment",
+			object(&[
+				("access_type", access_type(access)),
+				("contract_type", contract_type(contract)),
+				("contract_code", bytes(contract_code)),
+				("value", value.to_string()),
+				("salt", bytes(salt)),
+			]),
+		),
+		TransactionType::SmartContractInit(address, arguments) =>
+			variant("SmartContractInit", array(&[bytes(address), bytes(arguments)])),
+		TransactionType::SmartContractFunctionCall {
+			contract_instance_address,
+			function,
+			arguments,
+		} => variant(
+			"SmartContractFunctionCall",
+			object(&[
+				("contract_instance_address", bytes(contract_instance_address)),
+				("function", bytes(function)),
+				("arguments", bytes(arguments)),
+			]),
+		),
+		TransactionType::CreateStakingPool {
+			contract_instance_address,
+			min_stake,
+			max_stake,
+			min_pool_balance,
+			max_pool_balance,
+			staking_period,
+		} => staking_pool(
+			contract_instance_address,
+			min_stake,
+			max_stake,
+			min_pool_balance,
+			max_pool_balance,
+			staking_period,
+		),
+		TransactionType::Stake { pool_address, amount } => variant(
+			"Stake",
+			object(&[("pool_address", bytes(pool_address)), ("amount", amount.to_string())]),
+		),
+		TransactionType::UnStake { pool_address, amount } => variant(
+			"UnStake",
+			object(&[("pool_address", bytes(pool_address)), ("amount", amount.to_string())]),
+		),
+		TransactionType::StakingPoolContract { pool_address, contract_instance_address } =>
+			variant(
+				"StakingPoolContract",
+				object(&[
+					("pool_address", bytes(pool_address)),
+					("contract_instance_address", bytes(contract_instance_address)),
+				]),
+			),
+	}
+}
+
+fn transaction_type_v2(value: &TransactionTypeV2SignPayload) -> String {
+	match value {
+		TransactionTypeV2SignPayload::NativeTokenTransfer(address, amount) =>
+			variant("NativeTokenTransfer", array(&[bytes(address), string(amount)])),
+		TransactionTypeV2SignPayload::SmartContractDeployment {
+			access_type: access,
+			contract_type: contract,
+			contract_code,
+			deposit,
+			salt,
+		} => variant(
+			"SmartContractDeployment",
+			object(&[
+				("access_type", access_type(access)),
+				("contract_type", contract_type(contract)),
+				("contract_code", bytes(contract_code)),
+				("deposit", string(deposit)),
+				("salt", bytes(salt)),
+			]),
+		),
+		TransactionTypeV2SignPayload::SmartContractInit {
+			contract_code_address,
+			arguments,
+			deposit,
+		} => variant(
+			"SmartContractInit",
+			object(&[
+				("contract_code_address", bytes(contract_code_address)),
+				("arguments", bytes(arguments)),
+				("deposit", string(deposit)),
+			]),
+		),
+		TransactionTypeV2SignPayload::SmartContractFunctionCall {
+			contract_instance_address,
+			function,
+			arguments,
+			deposit,
+		} => variant(
+			"SmartContractFunctionCall",
+			object(&[
+				("contract_instance_address", bytes(contract_instance_address)),
+				("function", bytes(function)),
+				("arguments", bytes(arguments)),
+				("deposit", string(deposit)),
+			]),
+		),
+		TransactionTypeV2SignPayload::CreateStakingPool {
+			contract_instance_address,
+			min_stake,
+			max_stake,
+			min_pool_balance,
+			max_pool_balance,
+			staking_period,
+		} => staking_pool(
+			contract_instance_address,
+			min_stake,
+			max_stake,
+			min_pool_balance,
+			max_pool_balance,
+			staking_period,
+		),
+		TransactionTypeV2SignPayload::Stake { pool_address, amount } => variant(
+			"Stake",
+			object(&[("pool_address", bytes(pool_address)), ("amount", amount.to_string())]),
+		),
+		TransactionTypeV2SignPayload::UnStake { pool_address, amount } => variant(
+			"UnStake",
+			object(&[("pool_address", bytes(pool_address)), ("amount", amount.to_string())]),
+		),
+		TransactionTypeV2SignPayload::StakingPoolContract {
+			pool_address,
+			contract_instance_address,
+		} => variant(
+			"StakingPoolContract",
+			object(&[
+				("pool_address", bytes(pool_address)),
+				("contract_instance_address", bytes(contract_instance_address)),
+			]),
+		),
+	}
+}
+
+/// Canonical encoding of the `transaction_type` of a V2 payload on its own.
+pub fn encode_transaction_type_v2(transaction_type: &TransactionTypeV2SignPayload) -> Vec<u8> {
+	transaction_type_v2(transaction_type).into_bytes()
+}
+
+/// Canonical signing bytes of a V1 payload.
+pub fn encode_sign_payload(payload: &TXSignPayload) -> Vec<u8> {
+	object(&[
+		("nonce", payload.nonce.to_string()),
+		("transaction_type", transaction_type(&payload.transaction_type)),
+		("fee_limit", payload.fee_limit.to_string()),
+	])
+	.into_bytes()
+}
+
+/// Canonical signing bytes of a V2 payload.
+pub fn encode_sign_payload_v2(payload: &TXSignPayloadV2) -> Vec<u8> {
+	object(&[
+		("nonce", string(&payload.nonce)),
+		("transaction_type", transaction_type_v2(&payload.transaction_type)),
+		("fee_limit", string(&payload.fee_limit)),
+	])
+	.into_bytes()
+}
@@ -0,0 +1,363 @@
+//! secp256k1 keys and ECDSA over 32-byte digests.
+//!
+//! Two backends produce byte-identical keys and signatures: the `libsecp256k1` bindings of the
+//! `secp256k1` crate (feature `secp256k1`, enabled by default) and the pure-Rust `k256` crate
+//! (feature `k256`). `k256` takes precedence when both are enabled. Building with
+//! `--no-default-features --features k256` removes the C toolchain requirement and the gRPC
+//! transport, which is what `wasm32-unknown-unknown` builds need.
+//!
+//! Signatures are 64-byte compact `r || s` with low `s`, and high `s` signatures are rejected.
+//! Recoverable signatures append the recovery id, 0 or 1 when produced and 0, 1, 27 or 28 when
+//! parsed.
+
+use std::fmt;
+use zeroize::Zeroizing;
+
+#[cfg(not(any(feature = "secp256k1", feature = "k256")))]
+compile_error!("either the `secp256k1` or the `k256` feature must be enabled");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+	#[error("malformed or out of range secret key")]
+	InvalidSecretKey,
+	#[error("malformed public key")]
+	InvalidPublicKey,
+	#[error("malformed signature")]
+	InvalidSignature,
+	#[error("invalid recovery id")]
+	InvalidRecoveryId,
+	#[error("signature verification failed")]
+	IncorrectSignature,
+	#[error("tweak is out of range")]
+	InvalidTweak,
+}
+
+/// A secp256k1 secret key.
+///
+/// The key and each of its clones are zeroized on drop and never printed by `Debug` or `Display`.
+/// Signing may leave transient copies inside the backend.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretKey(backend::SecretKey);
+
+impl SecretKey {
+	pub fn from_slice(bytes: &[u8]) -> Result<Self, Error> {
+		if bytes.len() != 32 {
+			return Err(Error::InvalidSecretKey)
+		}
+		backend::secret_key_from_slice(bytes).map(Self)
+	}
+
+	/// Parses a hex encoded key with an optional `0x` prefix, wiping the decoded bytes afterwards.
+	pub fn from_hex(hex_str: &str) -> Result<Self, Error> {
+		let hex_str = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+		let bytes = Zeroizing::new(hex::decode(hex_str).map_err(|_| Error::InvalidSecretKey)?);
+		Self::from_slice(&bytes)
+	}
+
+	/// Generates a key with the thread-local CSPRNG.
+	pub fn random() -> Self {
+		Self(backend::random_secret_key())
+	}
+
+	/// The big-endian scalar, zeroized when the returned value is dropped.
+	pub fn secret_bytes(&self) -> Zeroizing<[u8; 32]> {
+		backend::secret_bytes(&self.0)
+	}
+
+	pub fn public_key(&self) -> PublicKey {
+		PublicKey(backend::public_key(&self.0))
+	}
+
+	/// Deterministic (RFC 6979) compact signature of `digest`.
+	pub fn sign_digest(&self, digest: &[u8; 32]) -> [u8; 64] {
+		backend::sign(&self.0, digest)
+	}
+
+	/// Deterministic (RFC 6979) compact signature of `digest` followed by the recovery id.
+	pub fn sign_digest_recoverable(&self, digest: &[u8; 32]) -> [u8; 65] {
+		backend::sign_recoverable(&self.0, digest)
+	}
+
+	/// Adds the big-endian scalar `tweak` to the key modulo the curve order, as in BIP-32.
+	pub fn add_tweak(&self, tweak: &[u8; 32]) -> Result<Self, Error> {
+		backend::add_tweak(&self.0, tweak).map(Self)
+	}
+}
+
+impl fmt::Debug for SecretKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "SecretKey(<redacted>)")
+	}
+}
+
+impl fmt::Display for SecretKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "<redacted>")
+	}
+}
+
+#[cfg(feature = "secp256k1")]
+impl From<secp256k1::SecretKey> for SecretKey {
+	fn from(value: secp256k1::SecretKey) -> Self {
+		Self::from_slice(&value.secret_bytes()).expect("secp256k1 secret keys are always valid")
+	}
+}
+
+/// A secp256k1 public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey(backend::PublicKey);
+
+impl PublicKey {
+	/// Parses a compressed (33 bytes) or uncompressed (65 bytes) SEC1 encoding.
+	pub fn from_slice(bytes: &[u8]) -> Result<Self, Error> {
+		if bytes.len() != 33 && bytes.len() != 65 {
+			return Err(Error::InvalidPublicKey)
+		}
+		backend::public_key_from_slice(bytes).map(Self)
+	}
+
+	/// Compressed SEC1 encoding.
+	pub fn serialize(&self) -> [u8; 33] {
+		backend::serialize_compressed(&self.0)
+	}
+
+	/// Uncompressed SEC1 encoding, `0x04 || x || y`.
+	pub fn serialize_uncompressed(&self) -> [u8; 65] {
+		backend::serialize_uncompressed(&self.0)
+	}
+
+	/// Checks the compact `signature` of `digest`.
+	pub fn verify_digest(&self, digest: &[u8; 32], signature: &[u8]) -> Result<(), Error> {
+		let signature: &[u8; 64] = signature.try_into().map_err(|_| Error::InvalidSignature)?;
+		backend::verify(&self.0, digest, signature)
+	}
+
+	/// Recovers the key that produced the recoverable `signature` of `digest`.
+	pub fn recover_from_digest(digest: &[u8; 32], signature: &[u8]) -> Result<Self, Error> {
+		let signature: &[u8; 65] = signature.try_into().map_err(|_| Error::InvalidSignature)?;
+		let recovery_id = match signature[64] {
+			v @ 0..=1 => v,
+			v @ 27..=28 => v - 27,
+			_ => return Err(Error::InvalidRecoveryId),
+		};
+		let compact = signature[..64].try_into().expect("slice of 64 bytes");
+		backend::recover(digest, compact, recovery_id).map(Self)
+	}
+}
+
+#[cfg(all(feature = "secp256k1", not(feature = "k256")))]
+mod backend {
+	use super::Error;
+	use secp256k1::{
+		ecdsa::{RecoverableSignature, RecoveryId, Signature},
+		Message, Scalar, SECP256K1,
+	};
+	use zeroize::Zeroizing;
+
+	/// `secp256k1::SecretKey` is `Copy` and cannot be zeroized, so the key is kept as validated
+	/// bytes and only turned into one for the duration of a call.
+	pub type SecretKey = Zeroizing<[u8; 32]>;
+	pub type PublicKey = secp256k1::PublicKey;
+
+	fn with_key<T>(key: &SecretKey, f: impl FnOnce(&secp256k1::SecretKey) -> T) -> T {
+		let mut key =
+			secp256k1::SecretKey::from_slice(&key[..]).expect("validated on construction");
+		let result = f(&key);
+		key.non_secure_erase();
+		result
+	}
+
+	pub fn secret_key_from_slice(bytes: &[u8]) -> Result<SecretKey, Error> {
+		let mut key =
+			secp256k1::SecretKey::from_slice(bytes).map_err(|_| Error::InvalidSecretKey)?;
+		key.non_secure_erase();
+		Ok(Zeroizing::new(bytes.try_into().expect("checked by the caller")))
+	}
+
+	pub fn random_secret_key() -> SecretKey {
+		let mut key = secp256k1::SecretKey::new(&mut rand::thread_rng());
+		let bytes = Zeroizing::new(key.secret_bytes());
+		key.non_secure_erase();
+		bytes
+	}
+
+	pub fn secret_bytes(key: &SecretKey) -> Zeroizing<[u8; 32]> {
+		key.clone()
+	}
+
+	pub fn public_key(key: &SecretKey) -> PublicKey {
+		with_key(key, |key| key.public_key(SECP256K1))
+	}
+
+	pub fn sign(key: &SecretKey, digest: &[u8; 32]) -> [u8; 64] {
+		with_key(key, |key| SECP256K1.sign_ecdsa(&Message::from_digest(*digest), key))
+			.serialize_compact()
+	}
+
+	pub fn sign_recoverable(key: &SecretKey, digest: &[u8; 32]) -> [u8; 65] {
+		let signature = with_key(key, |key| {
+			SECP256K1.sign_ecdsa_recoverable(&Message::from_digest(*digest), key)
+		});
+		let (recovery_id, compact) = signature.serialize_compact();
+		let mut bytes = [0u8; 65];
+		bytes[..64].copy_from_slice(&compact);
+		bytes[64] = recovery_id.to_i32() as u8;
+		bytes
+	}
+
+	pub fn add_tweak(key: &SecretKey, tweak: &[u8; 32]) -> Result<SecretKey, Error> {
+		let tweak = Scalar::from_be_bytes(*tweak).map_err(|_| Error::InvalidTweak)?;
+		let mut sum =
+			with_key(key, |key| key.add_tweak(&tweak)).map_err(|_| Error::InvalidTweak)?;
+		let bytes = Zeroizing::new(sum.secret_bytes());
+		sum.non_secure_erase();
+		Ok(bytes)
+	}
+
+	pub fn public_key_from_slice(bytes: &[u8]) -> Result<PublicKey, Error> {
+		PublicKey::from_slice(bytes).map_err(|_| Error::InvalidPublicKey)
+	}
+
+	pub fn serialize_compressed(key: &PublicKey) -> [u8; 33] {
+		key.serialize()
+	}
+
+	pub fn serialize_uncompressed(key: &PublicKey) -> [u8; 65] {
+		key.serialize_uncompressed()
+	}
+
+	pub fn verify(key: &PublicKey, digest: &[u8; 32], signature: &[u8; 64]) -> Result<(), Error> {
+		let signature = Signature::from_compact(signature).map_err(|_| Error::InvalidSignature)?;
+		SECP256K1
+			.verify_ecdsa(&Message::from_digest(*digest), &signature, key)
+			.map_err(|_| Error::IncorrectSignature)
+	}
+
+	pub fn recover(
+		digest: &[u8; 32],
+		signature: &[u8; 64],
+		recovery_id: u8,
+	) -> Result<PublicKey, Error> {
+		let recovery_id =
+			RecoveryId::from_i32(recovery_id as i32).map_err(|_| Error::InvalidRecoveryId)?;
+		let signature = RecoverableSignature::from_compact(signature, recovery_id)
+			.map_err(|_| Error::InvalidSignature)?;
+		SECP256K1
+			.recover_ecdsa(&Message::from_digest(*digest), &signature)
+			.map_err(|_| Error::IncorrectSignature)
+	}
+}
+
+#[cfg(feature = "k256")]
+mod backend {
+	use super::Error;
+	use k256::{
+		ecdsa::{
+			signature::hazmat::PrehashVerifier, RecoveryId, Signature, SigningKey, VerifyingKey,
+		},
+		elliptic_curve::{ff::PrimeField, ops::Reduce},
+		FieldBytes, NonZeroScalar, Scalar, U256,
+	};
+	use zeroize::Zeroizing;
+
+	pub type SecretKey = SigningKey;
+	pub type PublicKey = VerifyingKey;
+
+	pub fn secret_key_from_slice(bytes: &[u8]) -> Result<SecretKey, Error> {
+		SigningKey::from_slice(bytes).map_err(|_| Error::InvalidSecretKey)
+	}
+
+	pub fn random_secret_key() -> SecretKey {
+		SigningKey::random(&mut rand::thread_rng())
+	}
+
+	pub fn secret_bytes(key: &SecretKey) -> Zeroizing<[u8; 32]> {
+		Zeroizing::new(key.to_bytes().into())
+	}
+
+	pub fn public_key(key: &SecretKey) -> PublicKey {
+		*key.verifying_key()
+	}
+
+	fn sign_prehash(key: &SecretKey, digest: &[u8; 32]) -> (Signature, RecoveryId) {
+		// libsecp256k1 feeds the digest reduced modulo the order to RFC 6979, as the RFC requires.
+		let digest = <Scalar as Reduce<U256>>::reduce_bytes(&FieldBytes::from(*digest)).to_bytes();
+		// Only fails for an all-zero nonce, which RFC 6979 cannot produce for a valid key.
+		key.sign_prehash_recoverable(&digest).expect("RFC 6979 signing cannot fail")
+	}
+
+	pub fn sign(key: &SecretKey, digest: &[u8; 32]) -> [u8; 64] {
+		sign_prehash(key, digest).0.to_bytes().into()
+	}
+
+	pub fn sign_recoverable(key: &SecretKey, digest: &[u8; 32]) -> [u8; 65] {
+		let (signature, recovery_id) = sign_prehash(key, digest);
+		let mut bytes = [0u8; 65];
+		bytes[..64].copy_from_slice(&signature.to_bytes());
+		bytes[64] = recovery_id.to_byte();
+		bytes
+	}
+
+	pub fn add_tweak(key: &SecretKey, tweak: &[u8; 32]) -> Result<SecretKey, Error> {
+		let tweak = Option::<Scalar>::from(Scalar::from_repr((*tweak).into()))
+			.ok_or(Error::InvalidTweak)?;
+		let sum = Option::<NonZeroScalar>::from(NonZeroScalar::new(
+			*key.as_nonzero_scalar().as_ref() + tweak,
+		))
+		.ok_or(Error::InvalidTweak)?;
+		Ok(SigningKey::from(sum))
+	}
+
+	pub fn public_key_from_slice(bytes: &[u8]) -> Result<PublicKey, Error> {
+		VerifyingKey::from_sec1_bytes(bytes).map_err(|_| Error::InvalidPublicKey)
+	}
+
+	pub fn serialize_compressed(key: &PublicKey) -> [u8; 33] {
+		key.to_encoded_point(true)
+			.as_bytes()
+			.try_into()
+			.expect("compressed point is 33 bytes")
+	}
+
+	pub fn serialize_uncompressed(key: &PublicKey) -> [u8; 65] {
+		key.to_encoded_point(false)
+			.as_bytes()
+			.try_into()
+			.expect("uncompressed point is 65 bytes")
+	}
+
+	fn parse_signature(signature: &[u8; 64]) -> Result<Signature, Error> {
+		let in_range = |scalar: &[u8]| {
+			let scalar: [u8; 32] = scalar.try_into().expect("slice of 32 bytes");
+			bool::from(Scalar::from_repr(FieldBytes::from(scalar)).is_some())
+		};
+		if !in_range(&signature[..32]) || !in_range(&signature[32..]) {
+			return Err(Error::InvalidSignature)
+		}
+		// libsecp256k1 parses zero scalars and only fails to verify them.
+		Signature::from_slice(signature).map_err(|_| Error::IncorrectSignature)
+	}
+
+	pub fn verify(key: &PublicKey, digest: &[u8; 32], signature: &[u8; 64]) -> Result<(), Error> {
+		key.verify_prehash(digest, &parse_signature(signature)?)
+			.map_err(|_| Error::IncorrectSignature)
+	}
+
+	pub fn recover(
+		digest: &[u8; 32],
+		signature: &[u8; 64],
+		recovery_id: u8,
+	) -> Result<PublicKey, Error> {
+		let recovery_id = RecoveryId::from_byte(recovery_id).ok_or(Error::InvalidRecoveryId)?;
+		let signature = parse_signature(signature)?;
+		// libsecp256k1 recovers from high `s` too, which is the same key as the low `s` with the
+		// opposite `R`.
+		let (signature, recovery_id) = match signature.normalize_s() {
+			Some(low) =>
+				(low, RecoveryId::new(!recovery_id.is_y_odd(), recovery_id.is_x_reduced())),
+			None => (signature, recovery_id),
+		};
+		VerifyingKey::recover_from_prehash(digest, &signature, recovery_id)
+			.map_err(|_| Error::IncorrectSignature)
+	}
+}
@@ -0,0 +1,441 @@
+//! Human-readable descriptions of transactions and blocks, rendered as indented text or JSON.
+//!
+//! Addresses are shown in EIP-55 checksum form, function names as UTF-8 when they are valid
+//! UTF-8, contract arguments as JSON when they parse as JSON and balances in L1X with the raw
+//! amount alongside. Bytes that cannot be shown any other way are `0x`-prefixed hex.
+
+use crate::{
+	address::Address,
+	primitives::{self, Balance, Decimal},
+	rpc_model,
+	transaction::{
+		Transaction, TransactionType, TransactionTypeV2, TransactionV2, TransactionV3,
+		TransactionV3Response,
+	},
+};
+use serde_json::{json, Map};
+use std::fmt;
+
+/// Decimals of the native L1X token used to format balances.
+pub const NATIVE_DECIMALS: Decimal = 18;
+
+/// A value shown in a [`Description`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Field {
+	Text(String),
+	Json(serde_json::Value),
+	Nested(Description),
+	List(Vec<Description>),
+}
+
+impl From<String> for Field {
+	fn from(value: String) -> Self {
+		Field::Text(value)
+	}
+}
+
+impl From<&str> for Field {
+	fn from(value: &str) -> Self {
+		Field::Text(value.to_owned())
+	}
+}
+
+impl From<Description> for Field {
+	fn from(value: Description) -> Self {
+		Field::Nested(value)
+	}
+}
+
+/// A titled list of labelled fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Description {
+	pub title: String,
+	pub fields: Vec<(String, Field)>,
+}
+
+impl Description {
+	pub fn new(title: impl Into<String>) -> Self {
+		Self { title: title.into(), fields: Vec::new() }
+	}
+
+	pub fn field(mut self, label: impl Into<String>, value: impl Into<Field>) -> Self {
+		self.fields.push((label.into(), value.into()));
+		self
+	}
+
+	pub fn get(&self, label: &str) -> Option<&Field> {
+		self.fields.iter().find(|(l, _)| l == label).map(|(_, field)| field)
+	}
+
+	/// The description as a JSON object, its title under `"type"`.
+	pub fn to_json(&self) -> serde_json::Value {
+		let mut object = Map::new();
+		object.insert("type".to_owned(), json!(self.title));
+		for (label, field) in &self.fields {
+			let value = match field {
+				Field::Text(text) => json!(text),
+				Field::Json(value) => value.clone(),
+				Field::Nested(description) => description.to_json(),
+				Field::List(descriptions) =>
+					descriptions.iter().map(Description::to_json).collect(),
+			};
+			object.insert(label.clone(), value);
+		}
+		serde_json::Value::Object(object)
+	}
+
+	fn write_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+		let indent = "  ".repeat(depth + 1);
+		writeln!(f, "{}", self.title)?;
+		for (label, field) in &self.fields {
+			write!(f, "{}{}: ", indent, label)?;
+			match field {
+				Field::Text(text) => writeln!(f, "{}", text)?,
+				Field::Json(value) => writeln!(f, "{}", value)?,
+				Field::Nested(description) => description.write_indented(f, depth + 1)?,
+				Field::List(descriptions) => {
+					writeln!(f, "{} item(s)", descriptions.len())?;
+					for (i, description) in descriptions.iter().enumerate() {
+						write!(f, "{}  [{}] ", indent, i)?;
+						description.write_indented(f, depth + 2)?;
+					}
+				},
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Indented text, one field per line.
+impl fmt::Display for Description {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.write_indented(f, 0)
+	}
+}
+
+pub trait Describe {
+	fn describe(&self) -> Description;
+}
+
+/// Formats `amount` base units of a token with `decimals` decimals, e.g. `1.5` for
+/// `1500000000000000000` with 18 decimals.
+pub fn format_units(amount: Balance, decimals: Decimal) -> String {
+	let digits = format!("{:0>width$}", amount, width = decimals as usize + 1);
+	let (whole, fraction) = digits.split_at(digits.len() - decimals as usize);
+	let fraction = fraction.trim_end_matches('0');
+	if fraction.is_empty() {
+		whole.to_owned()
+	} else {
+		format!("{}.{}", whole, fraction)
+	}
+}
+
+fn balance(amount: Balance) -> Field {
+	Field::Text(format!("{} L1X ({})", format_units(amount, NATIVE_DECIMALS), amount))
+}
+
+fn address(address: &primitives::Address) -> Field {
+	Field::Text(Address(*address).to_checksum())
+}
+
+fn hex(bytes: &[u8]) -> Field {
+	Field::Text(format!("0x{}", hex::encode(bytes)))
+}
+
+/// UTF-8 text when valid, otherwise hex.
+fn text(bytes: &[u8]) -> Field {
+	match std::str::from_utf8(bytes) {
+		Ok(text) => Field::Text(text.to_owned()),
+		Err(_) => hex(bytes),
+	}
+}
+
+/// JSON when the bytes parse as JSON, otherwise UTF-8 text or hex.
+fn arguments(bytes: &[u8]) -> Field {
+	match serde_json::from_slice(bytes) {
+		Ok(value) => Field::Json(value),
+		Err(_) => text(bytes),
+	}
+}
+
+fn code(bytes: &[u8]) -> Field {
+	Field::Text(format!("{} bytes", bytes.len()))
+}
+
+fn optional<T>(value: &Option<T>, field: impl FnOnce(&T) -> Field) -> Field {
+	value.as_ref().map(field).unwrap_or(Field::Text("unset".to_owned()))
+}
+
+// Variants that are the same in `TransactionType` and `TransactionTypeV2`.
+
+fn native_token_transfer(to: &primitives::Address, amount: Balance) -> Description {
+	Description::new("Native token transfer")
+		.field("to", address(to))
+		.field("amount", balance(amount))
+}
+
+fn staking_pool(
+	contract_instance_address: &Option<primitives::Address>,
+	min_stake: &Option<Balance>,
+	max_stake: &Option<Balance>,
+	min_pool_balance: &Option<Balance>,
+	max_pool_balance: &Option<Balance>,
+	staking_period: &Option<primitives::BlockNumber>,
+) -> Description {
+	let mut description = Description::new("Create staking pool")
+		.field("contract", optional(contract_instance_address, address));
+	for (label, limit) in [
+		("min_stake", min_stake),
+		("max_stake", max_stake),
+		("min_pool_balance", min_pool_balance),
+		("max_pool_balance", max_pool_balance),
+	] {
+		description = description.field(label, optional(limit, |x| balance(*x)));
+	}
+	description.field("staking_period", optional(staking_period, |x| Field::Text(x.to_string())))
+}
+
+/// A stake or unstake of `amount` in the pool at `pool_address`.
+fn stake(title: &str, pool_address: &primitives::Address, amount: Balance) -> Description {
+	Description::new(title)
+		.field("pool", address(pool_address))
+		.field("amount", balance(amount))
+}
+
+fn staking_pool_contract(
+	pool_address: &primitives::Address,
+	contract_instance_address: &primitives::Address,
+) -> Description {
+	Description::new("Staking pool contract")
+		.field("pool", address(pool_address))
+		.field("contract", address(contract_instance_address))
+}
+
+impl Describe for TransactionTypeV2 {
+	fn describe(&self) -> Description {
+		match self {
+			TransactionTypeV2::NativeTokenTransfer(to, amount) =>
+				native_token_transfer(to, *amount),
+			TransactionTypeV2::SmartContractDeployment {
+				access_type,
+				contract_type,
+				contract_code,
+				deposit,
+				salt,
+			} => Description::new("Smart contract deployment")
+				.field("access_type", format!("{:?}", access_type))
+				.field("contract_type", format!("{:?}", contract_type))
+				.field("code", code(contract_code))
+				.field("deposit", balance(*deposit))
+				.field("salt", text(salt)),
+			TransactionTypeV2::SmartContractInit {
+				contract_code_address,
+				arguments: args,
+				deposit,
+			} => Description::new("Smart contract init")
+				.field("code_address", address(contract_code_address))
+				.field("arguments", arguments(args))
+				.field("deposit", balance(*deposit)),
+			TransactionTypeV2::SmartContractFunctionCall {
+				contract_instance_address,
+				function,
+				arguments: args,
+				deposit,
+			} => Description::new("Smart contract function call")
+				.field("contract", address(contract_instance_address))
+				.field("function", text(function))
+				.field("arguments", arguments(args))
+				.field("deposit", balance(*deposit)),
+			TransactionTypeV2::CreateStakingPool {
+				contract_instance_address,
+				min_stake,
+				max_stake,
+				min_pool_balance,
+				max_pool_balance,
+				staking_period,
+			} => staking_pool(
+				contract_instance_address,
+				min_stake,
+				max_stake,
+				min_pool_balance,
+				max_pool_balance,
+				staking_period,
+			),
+			TransactionTypeV2::Stake { pool_address, amount } =>
+				stake("Stake", pool_address, *amount),
+			TransactionTypeV2::UnStake { pool_address, amount } =>
+				stake("Unstake", pool_address, *amount),
+			TransactionTypeV2::StakingPoolContract { pool_address, contract_instance_address } =>
+				staking_pool_contract(pool_address, contract_instance_address),
+		}
+	}
+}
+
+impl Describe for TransactionType {
+	fn describe(&self) -> Description {
+		match self {
+			TransactionType::NativeTokenTransfer(to, amount) => native_token_transfer(to, *amount),
+			TransactionType::SmartContractDeployment {
+				access_type,
+				contract_type,
+				contract_code,
+				value,
+				salt,
+			} => Description::new("Smart contract deployment")
+				.field("access_type", format!("{:?}", access_type))
+				.field("contract_type", format!("{:?}", contract_type))
+				.field("code", code(contract_code))
+				.field("value", balance(*value))
+				.field("salt", text(salt)),
+			TransactionType::SmartContractInit(code_address, args) =>
+				Description::new("Smart contract init")
+					.field("code_address", address(code_address))
+					.field("arguments", arguments(args)),
+			TransactionType::SmartContractFunctionCall {
+				contract_instance_address,
+				function,
+				arguments: args,
+			} => Description::new("Smart contract function call")
+				.field("contract", address(contract_instance_address))
+				.field("function", text(function))
+				.field("arguments", arguments(args)),
+			TransactionType::CreateStakingPool {
+				contract_instance_address,
+				min_stake,
+				max_stake,
+				min_pool_balance,
+				max_pool_balance,
+				staking_period,
+			} => staking_pool(
+				contract_instance_address,
+				min_stake,
+				max_stake,
+				min_pool_balance,
+				max_pool_balance,
+				staking_period,
+			),
+			TransactionType::Stake { pool_address, amount } =>
+				stake("Stake", pool_address, *amount),
+			TransactionType::UnStake { pool_address, amount } =>
+				stake("Unstake", pool_address, *amount),
+			TransactionType::StakingPoolContract { pool_address, contract_instance_address } =>
+				staking_pool_contract(pool_address, contract_instance_address),
+		}
+	}
+}
+
+/// Fields shared by every transaction version.
+fn transaction(
+	title: &str,
+	nonce: primitives::Nonce,
+	fee_limit: Balance,
+	transaction_type: Description,
+	signature: &[u8],
+	verifying_key: &[u8],
+) -> Description {
+	let signer = match crate::address_from_verifying_key(verifying_key) {
+		Ok(signer) => Field::Text(signer.to_checksum()),
+		Err(_) => Field::Text("invalid verifying key".to_owned()),
+	};
+	Description::new(title)
+		.field("nonce", nonce.to_string())
+		.field("fee_limit", balance(fee_limit))
+		.field("transaction", transaction_type)
+		.field("signer", signer)
+		.field("signature", hex(signature))
+		.field("verifying_key", hex(verifying_key))
+}
+
+impl Describe for Transaction {
+	fn describe(&self) -> Description {
+		transaction(
+			"Transaction V1",
+			self.nonce,
+			self.fee_limit,
+			self.transaction_type.describe(),
+			&self.signature,
+			&self.verifying_key,
+		)
+	}
+}
+
+impl Describe for TransactionV2 {
+	fn describe(&self) -> Description {
+		let description = transaction(
+			"Transaction V2",
+			self.nonce,
+			self.fee_limit,
+			self.transaction_type.describe(),
+			&self.signature,
+			&self.verifying_key,
+		);
+		match &self.eth_original_transaction {
+			Some(eth_tx) => description.field("eth_original_transaction", hex(eth_tx)),
+			None => description,
+		}
+	}
+}
+
+impl Describe for TransactionV3 {
+	fn describe(&self) -> Description {
+		let description = transaction(
+			&format!("Transaction {:?}", self.version),
+			self.nonce,
+			self.fee_limit,
+			self.transaction_type.describe(),
+			&self.signature,
+			&self.verifying_key,
+		);
+		match &self.eth_original_transaction {
+			Some(eth_tx) => description.field("eth_original_transaction", hex(eth_tx)),
+			None => description,
+		}
+	}
+}
+
+impl Describe for TransactionV3Response {
+	fn describe(&self) -> Description {
+		Description::new("Included transaction")
+			.field("hash", hex(&self.transaction_hash))
+			.field("from", address(&self.from))
+			.field("block_number", self.block_number.to_string())
+			.field("block_hash", hex(&self.block_hash))
+			.field("fee_used", balance(self.fee_used))
+			.field("timestamp", self.timestamp.to_string())
+			.field("transaction", self.transaction.describe())
+	}
+}
+
+/// Describes the message as its domain type, or the reason it does not convert.
+impl Describe for rpc_model::TransactionV3Response {
+	fn describe(&self) -> Description {
+		match TransactionV3Response::try_from(self.clone()) {
+			Ok(response) => response.describe(),
+			Err(e) => Description::new("Invalid transaction")
+				.field("hash", hex(&self.transaction_hash))
+				.field("error", e.to_string()),
+		}
+	}
+}
+
+impl Describe for rpc_model::BlockV3 {
+	fn describe(&self) -> Description {
+		let block_type = rpc_model::BlockType::from_i32(self.block_type)
+			.map(|t| t.as_str_name().to_owned())
+			.unwrap_or_else(|| format!("unknown ({})", self.block_type));
+		Description::new("Block V3")
+			.field("number", self.number.as_str())
+			.field("hash", self.hash.as_str())
+			.field("parent_hash", self.parent_hash.as_str())
+			.field("timestamp", self.timestamp.to_string())
+			.field("block_type", block_type)
+			.field("cluster_address", self.cluster_address.as_str())
+			.field("state_hash", self.state_hash.as_str())
+			.field("block_version", self.block_version.as_str())
+			.field("epoch", self.epoch.as_str())
+			.field(
+				"transactions",
+				Field::List(self.transactions.iter().map(Describe::describe).collect()),
+			)
+	}
+}
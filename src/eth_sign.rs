@@ -0,0 +1,216 @@
+//! Ethereum wallet compatible signatures over L1X sign payloads.
+//!
+//! Browser wallets cannot sign the raw SHA-256 digest used by [`crate::sign_v2`], so this module
+//! provides two envelopes they do support:
+//! * EIP-191 `personal_sign` over the canonical signing bytes of a [`TXSignPayloadV2`]
+//! * EIP-712 typed data with the primary type `TXSignPayloadV2(string nonce,string fee_limit,string
+//!   transaction_type)`, where `transaction_type` is the canonical encoding of the transaction type
+//!
+//! Signatures are 65 bytes `r || s || v` with `v` 27 or 28, as produced by wallets.
+
+use crate::{address::Address, canonical, crypto, signer::Signer, TXSignPayloadV2, VerifyError};
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use sha3::{Digest, Keccak256};
+
+const EIP712_DOMAIN_TYPE: &str =
+	"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+const EIP712_DOMAIN_TYPE_NO_CONTRACT: &str =
+	"EIP712Domain(string name,string version,uint256 chainId)";
+const TX_SIGN_PAYLOAD_V2_TYPE: &str =
+	"TXSignPayloadV2(string nonce,string fee_limit,string transaction_type)";
+
+fn keccak(data: &[u8]) -> [u8; 32] {
+	Keccak256::digest(data).into()
+}
+
+/// Keccak-256 of the EIP-191 version `0x45` envelope of `message`.
+pub fn personal_message_hash(message: &[u8]) -> [u8; 32] {
+	let mut hasher = Keccak256::new();
+	hasher.update(format!("\x19Ethereum Signed Message:\n{}", message.len()).as_bytes());
+	hasher.update(message);
+	hasher.finalize().into()
+}
+
+async fn sign_eth_digest(signer: &dyn Signer, digest: &[u8; 32]) -> Result<[u8; 65]> {
+	let mut signature = crate::signer::sign_digest_recoverable(signer, digest).await?;
+	signature[64] += 27;
+	Ok(signature)
+}
+
+fn recover_eth_digest(digest: &[u8; 32], signature: &[u8]) -> Result<Address, crypto::Error> {
+	let public_key = crypto::PublicKey::recover_from_digest(digest, signature)?;
+	Ok(crate::address_from_public_key(&public_key))
+}
+
+fn verify_eth_digest(
+	digest: &[u8; 32],
+	signature: &[u8],
+	expected: &Address,
+) -> Result<(), VerifyError> {
+	let signer = recover_eth_digest(digest, signature).map_err(VerifyError::InvalidSignature)?;
+	if signer != *expected {
+		return Err(VerifyError::SignatureMismatch)
+	}
+	Ok(())
+}
+
+/// Signs the canonical bytes of `payload` as an EIP-191 `personal_sign` message.
+pub async fn personal_sign_v2(signer: &dyn Signer, payload: &TXSignPayloadV2) -> Result<[u8; 65]> {
+	sign_eth_digest(signer, &personal_message_hash(&payload.signing_bytes())).await
+}
+
+/// Recovers the address that produced a `personal_sign` signature over `payload`.
+pub fn recover_personal_sign_v2(payload: &TXSignPayloadV2, signature: &[u8]) -> Result<Address> {
+	Ok(recover_eth_digest(&personal_message_hash(&payload.signing_bytes()), signature)?)
+}
+
+/// Checks that `signature` is a `personal_sign` signature over `payload` by `expected`.
+pub fn verify_personal_sign_v2(
+	payload: &TXSignPayloadV2,
+	signature: &[u8],
+	expected: &Address,
+) -> Result<(), VerifyError> {
+	verify_eth_digest(&personal_message_hash(&payload.signing_bytes()), signature, expected)
+}
+
+/// EIP-712 domain L1X payloads are signed under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Eip712Domain {
+	pub name: String,
+	pub version: String,
+	pub chain_id: u64,
+	pub verifying_contract: Option<Address>,
+}
+
+impl Eip712Domain {
+	pub fn new(chain_id: u64) -> Self {
+		Self { name: "L1X".to_owned(), version: "1".to_owned(), chain_id, verifying_contract: None }
+	}
+
+	pub fn separator(&self) -> [u8; 32] {
+		let mut encoded = Vec::with_capacity(5 * 32);
+		let domain_type = match self.verifying_contract {
+			Some(_) => EIP712_DOMAIN_TYPE,
+			None => EIP712_DOMAIN_TYPE_NO_CONTRACT,
+		};
+		encoded.extend_from_slice(&keccak(domain_type.as_bytes()));
+		encoded.extend_from_slice(&keccak(self.name.as_bytes()));
+		encoded.extend_from_slice(&keccak(self.version.as_bytes()));
+		encoded.extend_from_slice(&[0u8; 24]);
+		encoded.extend_from_slice(&self.chain_id.to_be_bytes());
+		if let Some(contract) = &self.verifying_contract {
+			encoded.extend_from_slice(&[0u8; 12]);
+			encoded.extend_from_slice(contract.as_bytes());
+		}
+		keccak(&encoded)
+	}
+
+	fn to_json(&self) -> (Value, Value) {
+		let mut types = vec![
+			json!({ "name": "name", "type": "string" }),
+			json!({ "name": "version", "type": "string" }),
+			json!({ "name": "chainId", "type": "uint256" }),
+		];
+		let mut domain = json!({
+			"name": self.name,
+			"version": self.version,
+			"chainId": self.chain_id,
+		});
+		if let Some(contract) = &self.verifying_contract {
+			types.push(json!({ "name": "verifyingContract", "type": "address" }));
+			domain["verifyingContract"] = json!(contract.to_checksum());
+		}
+		(Value::Array(types), domain)
+	}
+}
+
+fn transaction_type_string(payload: &TXSignPayloadV2) -> Result<String> {
+	String::from_utf8(canonical::encode_transaction_type_v2(&payload.transaction_type))
+		.map_err(|e| anyhow!("Canonical transaction type is not UTF-8: {}", e))
+}
+
+/// EIP-712 `hashStruct` of a struct of type `encoded_type`, e.g. `Person(string name,address
+/// wallet)`, whose members are already encoded with `encodeData`.
+pub fn hash_struct(encoded_type: &str, encoded_members: &[[u8; 32]]) -> [u8; 32] {
+	let mut encoded = Vec::with_capacity((1 + encoded_members.len()) * 32);
+	encoded.extend_from_slice(&keccak(encoded_type.as_bytes()));
+	for member in encoded_members {
+		encoded.extend_from_slice(member);
+	}
+	keccak(&encoded)
+}
+
+/// EIP-712 `hashStruct` of `payload`.
+pub fn hash_struct_v2(payload: &TXSignPayloadV2) -> Result<[u8; 32]> {
+	Ok(hash_struct(
+		TX_SIGN_PAYLOAD_V2_TYPE,
+		&[
+			keccak(payload.nonce.as_bytes()),
+			keccak(payload.fee_limit.as_bytes()),
+			keccak(transaction_type_string(payload)?.as_bytes()),
+		],
+	))
+}
+
+/// EIP-712 digest `keccak256(0x19 0x01 || domainSeparator || hashStruct(message))`.
+pub fn typed_data_digest(domain: &Eip712Domain, struct_hash: &[u8; 32]) -> [u8; 32] {
+	let mut encoded = Vec::with_capacity(2 + 2 * 32);
+	encoded.extend_from_slice(&[0x19, 0x01]);
+	encoded.extend_from_slice(&domain.separator());
+	encoded.extend_from_slice(struct_hash);
+	keccak(&encoded)
+}
+
+/// Digest a wallet signs for `eth_signTypedData_v4` with [`typed_data_v2`].
+pub fn typed_data_digest_v2(domain: &Eip712Domain, payload: &TXSignPayloadV2) -> Result<[u8; 32]> {
+	Ok(typed_data_digest(domain, &hash_struct_v2(payload)?))
+}
+
+/// Typed data JSON to pass to a wallet's `eth_signTypedData_v4`.
+pub fn typed_data_v2(domain: &Eip712Domain, payload: &TXSignPayloadV2) -> Result<Value> {
+	let (domain_types, domain_value) = domain.to_json();
+	Ok(json!({
+		"types": {
+			"EIP712Domain": domain_types,
+			"TXSignPayloadV2": [
+				{ "name": "nonce", "type": "string" },
+				{ "name": "fee_limit", "type": "string" },
+				{ "name": "transaction_type", "type": "string" },
+			],
+		},
+		"primaryType": "TXSignPayloadV2",
+		"domain": domain_value,
+		"message": {
+			"nonce": payload.nonce,
+			"fee_limit": payload.fee_limit,
+			"transaction_type": transaction_type_string(payload)?,
+		},
+	}))
+}
+
+pub async fn sign_typed_data_v2(
+	signer: &dyn Signer,
+	domain: &Eip712Domain,
+	payload: &TXSignPayloadV2,
+) -> Result<[u8; 65]> {
+	sign_eth_digest(signer, &typed_data_digest_v2(domain, payload)?).await
+}
+
+pub fn recover_typed_data_v2(
+	domain: &Eip712Domain,
+	payload: &TXSignPayloadV2,
+	signature: &[u8],
+) -> Result<Address> {
+	Ok(recover_eth_digest(&typed_data_digest_v2(domain, payload)?, signature)?)
+}
+
+pub fn verify_typed_data_v2(
+	domain: &Eip712Domain,
+	payload: &TXSignPayloadV2,
+	signature: &[u8],
+	expected: &Address,
+) -> Result<(), VerifyError> {
+	let digest = typed_data_digest_v2(domain, payload).map_err(VerifyError::Payload)?;
+	verify_eth_digest(&digest, signature, expected)
+}
@@ -0,0 +1,92 @@
+//! Sender recovery for the raw Ethereum transactions carried in `eth_original_transaction`.
+//!
+//! Supports legacy transactions, with or without EIP-155 replay protection, and EIP-2718 typed
+//! transactions of type 1 (EIP-2930) and 2 (EIP-1559).
+
+use crate::{address::Address, crypto};
+use anyhow::{anyhow, Result};
+use rlp::{Rlp, RlpStream};
+use sha3::{Digest, Keccak256};
+
+const LEGACY_FIELDS: usize = 9;
+
+fn parse_list(bytes: &[u8]) -> Result<Rlp<'_>> {
+	let rlp = Rlp::new(bytes);
+	if !rlp.is_list() || rlp.payload_info()?.total() != bytes.len() {
+		return Err(anyhow!("Ethereum transaction is not a single RLP list"))
+	}
+	Ok(rlp)
+}
+
+fn signature(rlp: &Rlp, recovery_id: u8) -> Result<[u8; 65]> {
+	let count = rlp.item_count()?;
+	let mut signature = [0u8; 65];
+	for (i, offset) in [(count - 2, 0), (count - 1, 32)] {
+		let value = rlp.at(i)?.data()?;
+		if value.len() > 32 {
+			return Err(anyhow!("Invalid Ethereum signature scalar length {}", value.len()))
+		}
+		signature[offset + 32 - value.len()..offset + 32].copy_from_slice(value);
+	}
+	signature[64] = recovery_id;
+	Ok(signature)
+}
+
+/// Starts a list of `list_len` items with the first `count` items of `rlp`.
+fn unsigned_fields(rlp: &Rlp, count: usize, list_len: usize) -> Result<RlpStream> {
+	let mut stream = RlpStream::new_list(list_len);
+	for i in 0..count {
+		stream.append_raw(rlp.at(i)?.as_raw(), 1);
+	}
+	Ok(stream)
+}
+
+/// Digest the sender signed and the recoverable signature of the raw Ethereum transaction.
+pub fn signing_digest(raw: &[u8]) -> Result<([u8; 32], [u8; 65])> {
+	let first = *raw.first().ok_or_else(|| anyhow!("Empty Ethereum transaction"))?;
+	if first >= 0xc0 {
+		let rlp = parse_list(raw)?;
+		if rlp.item_count()? != LEGACY_FIELDS {
+			return Err(anyhow!("Invalid legacy transaction field count {}", rlp.item_count()?))
+		}
+		let v: u64 = rlp.val_at(6)?;
+		let (recovery_id, stream) = match v {
+			27 | 28 => ((v - 27) as u8, unsigned_fields(&rlp, 6, 6)?),
+			v if v >= 35 => {
+				let mut stream = unsigned_fields(&rlp, 6, 9)?;
+				stream.append(&((v - 35) / 2));
+				stream.append_empty_data();
+				stream.append_empty_data();
+				(((v - 35) % 2) as u8, stream)
+			},
+			v => return Err(anyhow!("Invalid legacy transaction v {}", v)),
+		};
+		let digest = Keccak256::digest(stream.out()).into();
+		return Ok((digest, signature(&rlp, recovery_id)?))
+	}
+
+	let fields = match first {
+		1 => 11,
+		2 => 12,
+		tx_type => return Err(anyhow!("Unsupported Ethereum transaction type {}", tx_type)),
+	};
+	let rlp = parse_list(&raw[1..])?;
+	if rlp.item_count()? != fields {
+		return Err(anyhow!("Invalid type {} transaction field count {}", first, rlp.item_count()?))
+	}
+	let y_parity: u8 = rlp.val_at(fields - 3)?;
+	if y_parity > 1 {
+		return Err(anyhow!("Invalid y parity {}", y_parity))
+	}
+	let mut hasher = Keccak256::new();
+	hasher.update([first]);
+	hasher.update(unsigned_fields(&rlp, fields - 3, fields - 3)?.out());
+	Ok((hasher.finalize().into(), signature(&rlp, y_parity)?))
+}
+
+/// Recovers the address that signed the raw Ethereum transaction.
+pub fn recover_sender(raw: &[u8]) -> Result<Address> {
+	let (digest, signature) = signing_digest(raw)?;
+	let public_key = crypto::PublicKey::recover_from_digest(&digest, &signature)?;
+	crate::address_from_verifying_key(&public_key.serialize())
+}
@@ -0,0 +1,119 @@
+//! Selection of a transaction's `fee_limit` from the node's `EstimateFee` figure.
+
+use crate::{
+	json,
+	primitives::*,
+	rpc_model::{estimate_fee_request, EstimateFeeRequest, EstimateFeeResponse},
+};
+use anyhow::{Context, Result};
+use reqwest::RequestBuilder;
+use serde_json::json;
+#[cfg(feature = "grpc")]
+use {crate::rpc_model::node_client::NodeClient, tonic::transport::Channel};
+
+/// How the `fee_limit` of a transaction is chosen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FeePolicy {
+	/// A fixed fee limit; the node is not asked for an estimate.
+	Fixed(Balance),
+	/// Exactly the node's estimate.
+	#[default]
+	Estimate,
+	/// The node's estimate plus `percent` percent of it, rounded up.
+	EstimatePlusMargin { percent: u32 },
+	/// The node's estimate, refused when it is above `cap`.
+	EstimateWithCap { cap: Balance },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum FeeError {
+	#[error("Fee estimate {estimate} is above the cap of {cap}")]
+	EstimateExceedsCap { estimate: Balance, cap: Balance },
+	#[error("Fee estimate {estimate} plus {percent}% overflows")]
+	Overflow { estimate: Balance, percent: u32 },
+}
+
+impl FeePolicy {
+	/// The fixed fee limit, if the policy does not need an estimate.
+	pub fn fixed(&self) -> Option<Balance> {
+		match self {
+			FeePolicy::Fixed(fee_limit) => Some(*fee_limit),
+			_ => None,
+		}
+	}
+
+	/// The fee limit the policy picks for the node's `estimate`.
+	pub fn apply(&self, estimate: Balance) -> Result<Balance, FeeError> {
+		match *self {
+			FeePolicy::Fixed(fee_limit) => Ok(fee_limit),
+			FeePolicy::Estimate => Ok(estimate),
+			FeePolicy::EstimatePlusMargin { percent } => {
+				let overflow = FeeError::Overflow { estimate, percent };
+				// Split at 100 so that only a margin that does not fit can overflow.
+				let percent = percent as Balance;
+				let rounded_up = (estimate % 100 * percent).div_ceil(100);
+				(estimate / 100)
+					.checked_mul(percent)
+					.and_then(|margin| margin.checked_add(rounded_up))
+					.and_then(|margin| estimate.checked_add(margin))
+					.ok_or(overflow)
+			},
+			FeePolicy::EstimateWithCap { cap } if estimate > cap =>
+				Err(FeeError::EstimateExceedsCap { estimate, cap }),
+			FeePolicy::EstimateWithCap { .. } => Ok(estimate),
+		}
+	}
+
+	/// Picks the fee limit for `transaction_type`, a `TransactionTypeV2` or a submit request's
+	/// transaction, sent by the owner of `verifying_key`. The node is asked for an estimate over
+	/// gRPC unless the policy is fixed.
+	#[cfg(feature = "grpc")]
+	pub async fn fee_limit_grpc(
+		&self,
+		client: &mut NodeClient<Channel>,
+		transaction_type: impl Into<estimate_fee_request::TransactionType>,
+		verifying_key: VerifyingKeyBytes,
+	) -> Result<Balance> {
+		if let Some(fee_limit) = self.fixed() {
+			return Ok(fee_limit)
+		}
+		let response = client
+			.estimate_fee(estimate_request(transaction_type, verifying_key))
+			.await?
+			.into_inner();
+		Ok(self.apply(parse_fee(&response)?)?)
+	}
+
+	/// Like [`FeePolicy::fee_limit_grpc`], over JSON-RPC.
+	pub async fn fee_limit_json_rpc(
+		&self,
+		client: RequestBuilder,
+		transaction_type: impl Into<estimate_fee_request::TransactionType>,
+		verifying_key: VerifyingKeyBytes,
+	) -> Result<Balance> {
+		if let Some(fee_limit) = self.fixed() {
+			return Ok(fee_limit)
+		}
+		let request = estimate_request(transaction_type, verifying_key);
+		let response =
+			json::post_json_rpc(client, "l1x_estimateFee", json!({ "request": request })).await?;
+		Ok(self.apply(parse_fee(&json::parse_response(response)?)?)?)
+	}
+}
+
+/// An `EstimateFeeRequest` for `transaction_type`, with the fee limit being estimated sent as
+/// zero.
+pub fn estimate_request(
+	transaction_type: impl Into<estimate_fee_request::TransactionType>,
+	verifying_key: VerifyingKeyBytes,
+) -> EstimateFeeRequest {
+	EstimateFeeRequest {
+		fee_limit: 0.to_string(),
+		transaction_type: Some(transaction_type.into()),
+		verifying_key,
+	}
+}
+
+pub fn parse_fee(response: &EstimateFeeResponse) -> Result<Balance> {
+	response.fee.parse().context("failed to parse fee estimate")
+}
@@ -1,8 +1,10 @@
-use crate::rpc_model::{GetAccountStateRequest, GetAccountStateResponse};
+use crate::{
+	rpc_model::{GetAccountStateRequest, GetAccountStateResponse},
+	signer::Signer,
+};
 use anyhow::{anyhow, Context, Result};
 use log::debug;
 use reqwest::RequestBuilder;
-use secp256k1::SecretKey;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{json, Value};
 
@@ -27,9 +29,13 @@ pub async fn post_json_rpc(
 	Ok(response)
 }
 
-pub async fn get_nonce(client: RequestBuilder, secret_key: &SecretKey) -> Result<u128> {
-	let address = crate::get_address_from_private_key(secret_key)?;
+pub async fn get_nonce(client: RequestBuilder, signer: &dyn Signer) -> Result<u128> {
+	let address = crate::get_address_from_verifying_key(&signer.verifying_key().await?)?;
+	get_account_nonce(client, address).await
+}
 
+/// The current nonce of the account at `address`, from its account state.
+pub async fn get_account_nonce(client: RequestBuilder, address: String) -> Result<u128> {
 	let response = post_json_rpc(
 		client,
 		"l1x_getAccountState",
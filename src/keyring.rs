@@ -0,0 +1,223 @@
+//! A set of labelled local accounts, resolved by address or label.
+//!
+//! Secret keys are held as [`SecretKey`]s, which are zeroized on drop and redacted from `Debug` and
+//! `Display`. Hex keys read from the environment are zeroized as soon as they are parsed, though
+//! the process environment itself keeps its copy.
+//!
+//! Bulk loads are all or nothing: when one key fails, none of the batch is added.
+
+use crate::{
+	address::Address,
+	crypto::{self, SecretKey},
+	keystore::{self, KeystoreError},
+	signer::LocalSigner,
+};
+use std::{
+	env, fmt, fs,
+	path::{Path, PathBuf},
+};
+use zeroize::Zeroizing;
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeyRingError {
+	#[error("label `{0}` is already in use")]
+	DuplicateLabel(String),
+	#[error("account {0} is already in the key ring")]
+	DuplicateAddress(Address),
+	#[error("invalid secret key for `{label}`: {source}")]
+	InvalidKey { label: String, source: crypto::Error },
+	#[error("environment variable {0} is not valid unicode")]
+	InvalidEnv(String),
+	#[error("environment variable prefix must not be empty")]
+	EmptyEnvPrefix,
+	#[error("failed to load keystore {path}: {source}")]
+	Keystore { path: PathBuf, source: KeystoreError },
+	#[error("key ring io error: {0}")]
+	Io(#[from] std::io::Error),
+}
+
+struct Account {
+	label: String,
+	address: Address,
+	signer: LocalSigner,
+}
+
+/// Labelled local accounts.
+#[derive(Default)]
+pub struct KeyRing {
+	accounts: Vec<Account>,
+}
+
+impl KeyRing {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds `secret_key` under `label` and returns its address. Labels and addresses are unique.
+	pub fn insert(
+		&mut self,
+		label: impl Into<String>,
+		secret_key: SecretKey,
+	) -> Result<Address, KeyRingError> {
+		let label = label.into();
+		let address = address_of_key(&secret_key);
+		self.check_unique(&label, &address)?;
+		self.accounts
+			.push(Account { label, address, signer: LocalSigner::new(secret_key) });
+		Ok(address)
+	}
+
+	/// Adds a hex encoded key under `label`, zeroizing the hex string once parsed.
+	pub fn insert_hex(
+		&mut self,
+		label: impl Into<String>,
+		private_key: Zeroizing<String>,
+	) -> Result<Address, KeyRingError> {
+		let label = label.into();
+		let secret_key = parse_hex(&label, &private_key)?;
+		self.insert(label, secret_key)
+	}
+
+	/// Decrypts the keystore at `path` and adds it under `label`.
+	pub fn load_keystore(
+		&mut self,
+		label: impl Into<String>,
+		path: &Path,
+		password: &str,
+	) -> Result<Address, KeyRingError> {
+		let (secret_key, _) = keystore::read_keystore(path, password)
+			.map_err(|source| KeyRingError::Keystore { path: path.to_owned(), source })?;
+		self.insert(label, secret_key)
+	}
+
+	/// Decrypts every `*.json` keystore in `dir` with `password`, labelling each account with its
+	/// file stem. Returns the added addresses in file name order.
+	pub fn load_keystore_dir(
+		&mut self,
+		dir: &Path,
+		password: &str,
+	) -> Result<Vec<Address>, KeyRingError> {
+		let mut paths = Vec::new();
+		for entry in fs::read_dir(dir)? {
+			let path = entry?.path();
+			if path.is_file() && path.extension().is_some_and(|ext| ext == "json") {
+				paths.push(path);
+			}
+		}
+		paths.sort();
+		self.insert_all(paths.iter().map(|path| {
+			let label = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+			let (secret_key, _) = keystore::read_keystore(path, password)
+				.map_err(|source| KeyRingError::Keystore { path: path.to_owned(), source })?;
+			Ok((label, secret_key))
+		}))
+	}
+
+	/// Adds a hex key from every environment variable named `<prefix><LABEL>`, labelling each
+	/// account with the lowercased `LABEL`. Returns the added addresses in variable name order.
+	pub fn load_env(&mut self, prefix: &str) -> Result<Vec<Address>, KeyRingError> {
+		if prefix.is_empty() {
+			return Err(KeyRingError::EmptyEnvPrefix)
+		}
+		let mut vars = Vec::new();
+		for (name, value) in env::vars_os() {
+			let Some(label) = name.to_str().and_then(|name| name.strip_prefix(prefix)) else {
+				continue
+			};
+			let value = value
+				.into_string()
+				.map_err(|_| KeyRingError::InvalidEnv(name.to_string_lossy().into_owned()))?;
+			vars.push((label.to_lowercase(), Zeroizing::new(value)));
+		}
+		vars.sort_by(|a, b| a.0.cmp(&b.0));
+		self.insert_all(vars.into_iter().map(|(label, value)| {
+			let secret_key = parse_hex(&label, &value)?;
+			Ok((label, secret_key))
+		}))
+	}
+
+	/// Adds every key of `keys`, or none of them if any key fails or is a duplicate.
+	fn insert_all(
+		&mut self,
+		keys: impl IntoIterator<Item = Result<(String, SecretKey), KeyRingError>>,
+	) -> Result<Vec<Address>, KeyRingError> {
+		let mut staged = Self::new();
+		for key in keys {
+			let (label, secret_key) = key?;
+			self.check_unique(&label, &address_of_key(&secret_key))?;
+			staged.insert(label, secret_key)?;
+		}
+		let addresses = staged.accounts.iter().map(|account| account.address).collect();
+		self.accounts.append(&mut staged.accounts);
+		Ok(addresses)
+	}
+
+	fn check_unique(&self, label: &str, address: &Address) -> Result<(), KeyRingError> {
+		if self.accounts.iter().any(|account| account.label == label) {
+			return Err(KeyRingError::DuplicateLabel(label.to_owned()))
+		}
+		if self.contains(address) {
+			return Err(KeyRingError::DuplicateAddress(*address))
+		}
+		Ok(())
+	}
+
+	pub fn contains(&self, address: &Address) -> bool {
+		self.by_address(address).is_some()
+	}
+
+	pub fn by_address(&self, address: &Address) -> Option<&LocalSigner> {
+		self.accounts
+			.iter()
+			.find(|account| account.address == *address)
+			.map(|a| &a.signer)
+	}
+
+	pub fn by_label(&self, label: &str) -> Option<&LocalSigner> {
+		self.accounts.iter().find(|account| account.label == label).map(|a| &a.signer)
+	}
+
+	/// Resolves `key` as a label first and then as an address.
+	pub fn resolve(&self, key: &str) -> Option<&LocalSigner> {
+		self.by_label(key).or_else(|| self.by_address(&key.parse().ok()?))
+	}
+
+	pub fn address_of(&self, label: &str) -> Option<Address> {
+		self.accounts.iter().find(|account| account.label == label).map(|a| a.address)
+	}
+
+	pub fn remove(&mut self, address: &Address) -> bool {
+		let len = self.accounts.len();
+		self.accounts.retain(|account| account.address != *address);
+		self.accounts.len() != len
+	}
+
+	/// Labels and addresses of all accounts, in insertion order.
+	pub fn accounts(&self) -> impl Iterator<Item = (&str, Address)> {
+		self.accounts.iter().map(|account| (account.label.as_str(), account.address))
+	}
+
+	pub fn len(&self) -> usize {
+		self.accounts.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.accounts.is_empty()
+	}
+}
+
+impl fmt::Debug for KeyRing {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_map().entries(self.accounts()).finish()
+	}
+}
+
+fn address_of_key(secret_key: &SecretKey) -> Address {
+	crate::address_from_verifying_key(&secret_key.public_key().serialize())
+		.expect("a secret key always has a valid public key")
+}
+
+fn parse_hex(label: &str, private_key: &str) -> Result<SecretKey, KeyRingError> {
+	SecretKey::from_hex(private_key.trim())
+		.map_err(|source| KeyRingError::InvalidKey { label: label.to_owned(), source })
+}
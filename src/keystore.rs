@@ -0,0 +1,298 @@
+//! Client side Web3 Secret Storage (version 3) keystores for L1X accounts.
+
+use crate::{
+	crypto::{self, SecretKey},
+	rpc_model::{CreateAccountRequest, ImportAccountRequest},
+};
+use aes::cipher::{KeyIvInit, StreamCipher};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+const KEYSTORE_VERSION: u32 = 3;
+const CIPHER: &str = "aes-128-ctr";
+const DKLEN: usize = 32;
+
+// Keystore files are untrusted, so their KDF parameters are bounded before anything is allocated.
+// scrypt then uses at most 128 * r * n = 1 GiB.
+const MAX_DKLEN: usize = 64;
+const MAX_SCRYPT_LOG_N: u32 = 20;
+const MAX_SCRYPT_R: u32 = 8;
+const MAX_SCRYPT_P: u32 = 16;
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeystoreError {
+	#[error("keystore io error: {0}")]
+	Io(#[from] std::io::Error),
+	#[error("malformed keystore: {0}")]
+	Json(#[from] serde_json::Error),
+	#[error("malformed keystore field {field}: {reason}")]
+	Malformed { field: &'static str, reason: String },
+	#[error("unsupported keystore version {0}")]
+	UnsupportedVersion(u32),
+	#[error("unsupported cipher {0}")]
+	UnsupportedCipher(String),
+	#[error("invalid kdf parameters: {0}")]
+	InvalidKdfParams(String),
+	#[error("MAC mismatch, the password is wrong or the keystore is corrupted")]
+	MacMismatch,
+	#[error("invalid private key: {0}")]
+	InvalidKey(#[from] crypto::Error),
+	#[error("failed to derive address: {0}")]
+	Address(anyhow::Error),
+}
+
+/// Key derivation function used to turn the password into the encryption key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kdf {
+	Scrypt { log_n: u8, r: u32, p: u32 },
+	Pbkdf2 { iterations: u32 },
+}
+
+impl Default for Kdf {
+	fn default() -> Self {
+		Kdf::Scrypt { log_n: 18, r: 8, p: 1 }
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+	#[serde(default)]
+	pub address: String,
+	pub crypto: CryptoSection,
+	pub id: String,
+	pub version: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CryptoSection {
+	pub cipher: String,
+	pub cipherparams: CipherParams,
+	pub ciphertext: String,
+	#[serde(flatten)]
+	pub kdf: KdfSection,
+	pub mac: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CipherParams {
+	pub iv: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kdf", content = "kdfparams", rename_all = "lowercase")]
+pub enum KdfSection {
+	Scrypt { dklen: usize, n: u64, r: u32, p: u32, salt: String },
+	Pbkdf2 { c: u32, dklen: usize, prf: String, salt: String },
+}
+
+/// A decrypted keystore account.
+pub struct KeystoreAccount {
+	pub secret_key: SecretKey,
+	pub address: String,
+	pub path: PathBuf,
+}
+
+fn decode_field(field: &'static str, value: &str) -> Result<Vec<u8>, KeystoreError> {
+	hex::decode(value.trim_start_matches("0x"))
+		.map_err(|e| KeystoreError::Malformed { field, reason: e.to_string() })
+}
+
+fn keystore_mac(derived_key: &[u8], ciphertext: &[u8]) -> [u8; 32] {
+	let mut hasher = Keccak256::new();
+	hasher.update(&derived_key[16..32]);
+	hasher.update(ciphertext);
+	hasher.finalize().into()
+}
+
+impl KdfSection {
+	fn derive_key(&self, password: &[u8]) -> Result<Vec<u8>, KeystoreError> {
+		match self {
+			KdfSection::Scrypt { dklen, n, r, p, salt } => {
+				let invalid = || {
+					KeystoreError::InvalidKdfParams(format!(
+						"dklen {} n {} r {} p {}",
+						dklen, n, r, p
+					))
+				};
+				if !(DKLEN..=MAX_DKLEN).contains(dklen) ||
+					!n.is_power_of_two() ||
+					!(1..=MAX_SCRYPT_LOG_N).contains(&n.trailing_zeros()) ||
+					!(1..=MAX_SCRYPT_R).contains(r) ||
+					!(1..=MAX_SCRYPT_P).contains(p)
+				{
+					return Err(invalid())
+				}
+				let params = scrypt::Params::new(n.trailing_zeros() as u8, *r, *p, *dklen)
+					.map_err(|_| invalid())?;
+				let mut key = vec![0u8; *dklen];
+				scrypt::scrypt(password, &decode_field("kdfparams.salt", salt)?, &params, &mut key)
+					.map_err(|_| invalid())?;
+				Ok(key)
+			},
+			KdfSection::Pbkdf2 { c, dklen, prf, salt } => {
+				if prf != "hmac-sha256" {
+					return Err(KeystoreError::InvalidKdfParams(format!("unsupported prf {}", prf)))
+				}
+				if !(DKLEN..=MAX_DKLEN).contains(dklen) {
+					return Err(KeystoreError::InvalidKdfParams(format!("dklen {}", dklen)))
+				}
+				let mut key = vec![0u8; *dklen];
+				pbkdf2::pbkdf2_hmac::<Sha256>(
+					password,
+					&decode_field("kdfparams.salt", salt)?,
+					*c,
+					&mut key,
+				);
+				Ok(key)
+			},
+		}
+	}
+}
+
+impl Keystore {
+	/// Encrypts `secret_key` under `password`.
+	pub fn encrypt(secret_key: &SecretKey, password: &str, kdf: Kdf) -> Result<Self, KeystoreError> {
+		let mut rng = rand::thread_rng();
+		let mut salt = [0u8; 32];
+		let mut iv = [0u8; 16];
+		rng.fill_bytes(&mut salt);
+		rng.fill_bytes(&mut iv);
+
+		let kdf = match kdf {
+			Kdf::Scrypt { log_n, r, p } => KdfSection::Scrypt {
+				dklen: DKLEN,
+				n: 1u64.checked_shl(log_n as u32).ok_or_else(|| {
+					KeystoreError::InvalidKdfParams(format!("log_n {}", log_n))
+				})?,
+				r,
+				p,
+				salt: hex::encode(salt),
+			},
+			Kdf::Pbkdf2 { iterations } => KdfSection::Pbkdf2 {
+				c: iterations,
+				dklen: DKLEN,
+				prf: "hmac-sha256".to_owned(),
+				salt: hex::encode(salt),
+			},
+		};
+		let derived_key = Zeroizing::new(kdf.derive_key(password.as_bytes())?);
+
+		let mut ciphertext = secret_key.secret_bytes().to_vec();
+		Aes128Ctr::new(derived_key[..16].into(), &iv.into()).apply_keystream(&mut ciphertext);
+
+		Ok(Self {
+			address: crate::get_address_from_private_key(secret_key)
+				.map_err(KeystoreError::Address)?,
+			crypto: CryptoSection {
+				cipher: CIPHER.to_owned(),
+				cipherparams: CipherParams { iv: hex::encode(iv) },
+				ciphertext: hex::encode(&ciphertext),
+				mac: hex::encode(keystore_mac(&derived_key, &ciphertext)),
+				kdf,
+			},
+			id: uuid::Uuid::new_v4().to_string(),
+			version: KEYSTORE_VERSION,
+		})
+	}
+
+	/// Checks the MAC and decrypts the secret key. The stored address must match the decrypted
+	/// key.
+	pub fn decrypt(&self, password: &str) -> Result<SecretKey, KeystoreError> {
+		if self.version != KEYSTORE_VERSION {
+			return Err(KeystoreError::UnsupportedVersion(self.version))
+		}
+		if self.crypto.cipher != CIPHER {
+			return Err(KeystoreError::UnsupportedCipher(self.crypto.cipher.clone()))
+		}
+
+		let derived_key = Zeroizing::new(self.crypto.kdf.derive_key(password.as_bytes())?);
+		let mut ciphertext =
+			Zeroizing::new(decode_field("crypto.ciphertext", &self.crypto.ciphertext)?);
+		let mac = decode_field("crypto.mac", &self.crypto.mac)?;
+		if !bool::from(keystore_mac(&derived_key, &ciphertext)[..].ct_eq(&mac)) {
+			return Err(KeystoreError::MacMismatch)
+		}
+
+		let iv: [u8; 16] = decode_field("crypto.cipherparams.iv", &self.crypto.cipherparams.iv)?
+			.try_into()
+			.map_err(|iv: Vec<u8>| KeystoreError::Malformed {
+				field: "crypto.cipherparams.iv",
+				reason: format!("expected 16 bytes, got {}", iv.len()),
+			})?;
+		Aes128Ctr::new(derived_key[..16].into(), &iv.into()).apply_keystream(&mut ciphertext);
+		let secret_key = SecretKey::from_slice(&ciphertext)?;
+
+		let address = crate::get_address_from_private_key(&secret_key)
+			.map_err(KeystoreError::Address)?;
+		if !self.address.is_empty() && address != self.address.trim_start_matches("0x").to_lowercase()
+		{
+			return Err(KeystoreError::Malformed {
+				field: "address",
+				reason: format!("keystore is for {} but decrypts to {}", self.address, address),
+			})
+		}
+		Ok(secret_key)
+	}
+}
+
+/// Encrypts `secret_key` into `<address>.json` inside `keystore_dir` and returns the file path.
+pub fn write_keystore(
+	keystore_dir: &Path,
+	secret_key: &SecretKey,
+	password: &str,
+	kdf: Kdf,
+) -> Result<PathBuf, KeystoreError> {
+	let keystore = Keystore::encrypt(secret_key, password, kdf)?;
+	fs::create_dir_all(keystore_dir)?;
+	let path = keystore_dir.join(format!("{}.json", keystore.address));
+	fs::write(&path, serde_json::to_vec_pretty(&keystore)?)?;
+	Ok(path)
+}
+
+/// Decrypts the keystore at `path` and returns its secret key and address.
+pub fn read_keystore(path: &Path, password: &str) -> Result<(SecretKey, String), KeystoreError> {
+	let keystore: Keystore = serde_json::from_slice(&fs::read(path)?)?;
+	let secret_key = keystore.decrypt(password)?;
+	let address = crate::get_address_from_private_key(&secret_key).map_err(KeystoreError::Address)?;
+	Ok((secret_key, address))
+}
+
+/// Generates a new account and stores it in `request.keystore_path` without contacting a node.
+pub fn create_account(
+	request: &CreateAccountRequest,
+	kdf: Kdf,
+) -> Result<KeystoreAccount, KeystoreError> {
+	let secret_key = SecretKey::random();
+	store_account(secret_key, &request.password, &request.keystore_path, kdf)
+}
+
+/// Stores `request.private_key` in `request.keystore_path` without contacting a node.
+pub fn import_account(
+	request: &ImportAccountRequest,
+	kdf: Kdf,
+) -> Result<KeystoreAccount, KeystoreError> {
+	let private_key = Zeroizing::new(decode_field("private_key", &request.private_key)?);
+	let secret_key = SecretKey::from_slice(&private_key)?;
+	store_account(secret_key, &request.password, &request.keystore_path, kdf)
+}
+
+fn store_account(
+	secret_key: SecretKey,
+	password: &str,
+	keystore_path: &str,
+	kdf: Kdf,
+) -> Result<KeystoreAccount, KeystoreError> {
+	let path = write_keystore(Path::new(keystore_path), &secret_key, password, kdf)?;
+	let address = crate::get_address_from_private_key(&secret_key).map_err(KeystoreError::Address)?;
+	Ok(KeystoreAccount { secret_key, address, path })
+}
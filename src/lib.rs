@@ -1,29 +1,44 @@
+pub mod address;
+pub mod builder;
+pub mod canonical;
+pub mod crypto;
+pub mod describe;
+pub mod eth_sign;
+pub mod eth_transaction;
+pub mod fee;
 pub mod json;
+pub mod keyring;
+pub mod keystore;
+pub mod nonce;
+pub mod offline;
 pub mod primitives;
+pub mod signer;
+pub mod simulate;
+#[cfg(feature = "tracker")]
+pub mod tracker;
 pub mod transaction;
+pub mod upgrade;
+pub mod wallet;
 use crate::{primitives::*, transaction::TransactionType};
 use anyhow::{anyhow, Result};
-use k256::{elliptic_curve::sec1::ToEncodedPoint, PublicKey as K256PublicKey};
-use secp256k1::{hashes::sha256, Message, Secp256k1, SecretKey};
+use crypto::SecretKey;
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 use sha3::{Digest, Keccak256};
+use signer::Signer;
 use transaction::TransactionTypeV2;
 
 pub mod rpc_model {
 	tonic::include_proto!("rpc_model");
+	use crate::address::{Address, AddressError};
 	use anyhow::anyhow;
 	use std::str::FromStr;
 
-	impl From<SubmitTransactionRequest> for SubmitTransactionRequestV2 {
-		fn from(value: SubmitTransactionRequest) -> Self {
-			Self {
-				nonce: value.nonce,
-				fee_limit: value.fee_limit,
-				transaction_type: value.transaction_type.and_then(|v| Some(v.into())),
-				verifying_key: value.verifying_key,
-				signature: value.signature,
-			}
-		}
+	fn address_field(
+		bytes: &[u8],
+		field: &'static str,
+	) -> Result<crate::primitives::Address, AddressError> {
+		Address::from_field(bytes, field).map(Into::into)
 	}
 
 	impl From<submit_transaction_request::TransactionType> for submit_transaction_request_v2::TransactionType {
@@ -76,7 +91,7 @@ pub mod rpc_model {
 				submit_transaction_request::TransactionType::NativeTokenTransfer(
 					NativeTokenTransfer { address, amount },
 				) => super::transaction::TransactionType::NativeTokenTransfer(
-					address.try_into().map_err(|_| anyhow!("Failed to convert address bytes"))?,
+					address_field(&address, "native_token_transfer.address")?,
 					u128::from_str(&amount)
 						.map_err(|_| anyhow!("Failed to convert string to u128"))?,
 				),
@@ -98,15 +113,16 @@ pub mod rpc_model {
 				submit_transaction_request::TransactionType::SmartContractInit(
 					SmartContractInit { address, arguments },
 				) => super::transaction::TransactionType::SmartContractInit(
-					address.try_into().map_err(|_| anyhow!("Failed to convert address bytes"))?,
+					address_field(&address, "smart_contract_init.address")?,
 					arguments,
 				),
 				submit_transaction_request::TransactionType::SmartContractFunctionCall(
 					SmartContractFunctionCall { contract_address, function_name, arguments },
 				) => super::transaction::TransactionType::SmartContractFunctionCall {
-					contract_instance_address: contract_address
-						.try_into()
-						.map_err(|_| anyhow!("Failed to convert contract_address bytes"))?,
+					contract_instance_address: address_field(
+						&contract_address,
+						"smart_contract_function_call.contract_address",
+					)?,
 					function: function_name,
 					arguments,
 				},
@@ -121,25 +137,33 @@ pub mod rpc_model {
 					},
 				) => super::transaction::TransactionType::CreateStakingPool {
 					contract_instance_address: match contract_instance_address {
-						Some(x) => Some(x.try_into().map_err(|_| {
-							anyhow!("Failed to convert contract_instance_address bytes")
-						})?),
+						Some(x) => Some(address_field(
+							&x,
+							"create_staking_pool.contract_instance_address",
+						)?),
 						None => None,
 					},
 
-					min_stake: min_stake.and_then(|s| s.parse().ok()),
-					max_stake: max_stake.and_then(|s| s.parse().ok()),
-					min_pool_balance: min_pool_balance.and_then(|s| s.parse().ok()),
-					max_pool_balance: max_pool_balance.and_then(|s| s.parse().ok()),
-					staking_period: staking_period.and_then(|s| s.parse().ok()),
+					min_stake: optional_number_field(min_stake, "create_staking_pool.min_stake")?,
+					max_stake: optional_number_field(max_stake, "create_staking_pool.max_stake")?,
+					min_pool_balance: optional_number_field(
+						min_pool_balance,
+						"create_staking_pool.min_pool_balance",
+					)?,
+					max_pool_balance: optional_number_field(
+						max_pool_balance,
+						"create_staking_pool.max_pool_balance",
+					)?,
+					staking_period: optional_number_field(
+						staking_period,
+						"create_staking_pool.staking_period",
+					)?,
 				},
 				submit_transaction_request::TransactionType::Stake(Stake {
 					pool_address,
 					amount,
 				}) => super::transaction::TransactionType::Stake {
-					pool_address: pool_address
-						.try_into()
-						.map_err(|_| anyhow!("Failed to convert pool_address bytes"))?,
+					pool_address: address_field(&pool_address, "stake.pool_address")?,
 					amount: u128::from_str(&amount)
 						.map_err(|_| anyhow!("Failed to convert string to u128"))?,
 				},
@@ -147,9 +171,7 @@ pub mod rpc_model {
 					pool_address,
 					amount,
 				}) => super::transaction::TransactionType::UnStake {
-					pool_address: pool_address
-						.try_into()
-						.map_err(|_| anyhow!("Failed to convert pool_address bytes"))?,
+					pool_address: address_field(&pool_address, "unstake.pool_address")?,
 					amount: u128::from_str(&amount)
 						.map_err(|_| anyhow!("Failed to convert string to u128"))?,
 				},
@@ -168,7 +190,7 @@ pub mod rpc_model {
 				submit_transaction_request_v2::TransactionType::NativeTokenTransfer(
 					NativeTokenTransfer { address, amount },
 				) => super::transaction::TransactionTypeV2::NativeTokenTransfer(
-					address.try_into().map_err(|_| anyhow!("Failed to convert address bytes"))?,
+					address_field(&address, "native_token_transfer.address")?,
 					u128::from_str(&amount)
 						.map_err(|_| anyhow!("Failed to convert string to u128"))?,
 				),
@@ -190,16 +212,20 @@ pub mod rpc_model {
 				submit_transaction_request_v2::TransactionType::SmartContractInit(
 					SmartContractInitV2 { contract_code_address, arguments, deposit },
 				) => super::transaction::TransactionTypeV2::SmartContractInit{
-					contract_code_address: contract_code_address.try_into().map_err(|_| anyhow!("Failed to convert address bytes"))?,
-					arguments: arguments,
+					contract_code_address: address_field(
+						&contract_code_address,
+						"smart_contract_init.contract_code_address",
+					)?,
+					arguments,
 					deposit: crate::Balance::from_str(&deposit).map_err(|_| anyhow!("Failed to convert string to Balance"))?,
 				},
 				submit_transaction_request_v2::TransactionType::SmartContractFunctionCall(
 					SmartContractFunctionCallV2 { contract_instance_address, function_name, arguments, deposit },
 				) => super::transaction::TransactionTypeV2::SmartContractFunctionCall {
-					contract_instance_address: contract_instance_address
-						.try_into()
-						.map_err(|_| anyhow!("Failed to convert contract_instance_address bytes"))?,
+					contract_instance_address: address_field(
+						&contract_instance_address,
+						"smart_contract_function_call.contract_instance_address",
+					)?,
 					function: function_name,
 					arguments,
 					deposit: crate::Balance::from_str(&deposit).map_err(|_| anyhow!("Failed to convert string to Balance"))?,
@@ -215,25 +241,33 @@ pub mod rpc_model {
 					},
 				) => super::transaction::TransactionTypeV2::CreateStakingPool {
 					contract_instance_address: match contract_instance_address {
-						Some(x) => Some(x.try_into().map_err(|_| {
-							anyhow!("Failed to convert contract_instance_address bytes")
-						})?),
+						Some(x) => Some(address_field(
+							&x,
+							"create_staking_pool.contract_instance_address",
+						)?),
 						None => None,
 					},
 
-					min_stake: min_stake.and_then(|s| s.parse().ok()),
-					max_stake: max_stake.and_then(|s| s.parse().ok()),
-					min_pool_balance: min_pool_balance.and_then(|s| s.parse().ok()),
-					max_pool_balance: max_pool_balance.and_then(|s| s.parse().ok()),
-					staking_period: staking_period.and_then(|s| s.parse().ok()),
+					min_stake: optional_number_field(min_stake, "create_staking_pool.min_stake")?,
+					max_stake: optional_number_field(max_stake, "create_staking_pool.max_stake")?,
+					min_pool_balance: optional_number_field(
+						min_pool_balance,
+						"create_staking_pool.min_pool_balance",
+					)?,
+					max_pool_balance: optional_number_field(
+						max_pool_balance,
+						"create_staking_pool.max_pool_balance",
+					)?,
+					staking_period: optional_number_field(
+						staking_period,
+						"create_staking_pool.staking_period",
+					)?,
 				},
 				submit_transaction_request_v2::TransactionType::Stake(Stake {
 					pool_address,
 					amount,
 				}) => super::transaction::TransactionTypeV2::Stake {
-					pool_address: pool_address
-						.try_into()
-						.map_err(|_| anyhow!("Failed to convert pool_address bytes"))?,
+					pool_address: address_field(&pool_address, "stake.pool_address")?,
 					amount: u128::from_str(&amount)
 						.map_err(|_| anyhow!("Failed to convert string to u128"))?,
 				},
@@ -241,9 +275,7 @@ pub mod rpc_model {
 					pool_address,
 					amount,
 				}) => super::transaction::TransactionTypeV2::UnStake {
-					pool_address: pool_address
-						.try_into()
-						.map_err(|_| anyhow!("Failed to convert pool_address bytes"))?,
+					pool_address: address_field(&pool_address, "unstake.pool_address")?,
 					amount: u128::from_str(&amount)
 						.map_err(|_| anyhow!("Failed to convert string to u128"))?,
 				},
@@ -252,6 +284,28 @@ pub mod rpc_model {
 		}
 	}
 
+	/// The transaction the node builds from a `SubmitTransactionV2` call.
+	impl TryFrom<SubmitTransactionRequestV2> for super::transaction::TransactionV3 {
+		type Error = anyhow::Error;
+
+		fn try_from(value: SubmitTransactionRequestV2) -> Result<Self, Self::Error> {
+			Ok(Self {
+				version: super::transaction::TransactionVersion::V3,
+				nonce: crate::Nonce::from_str(&value.nonce)
+					.map_err(|_| anyhow!("Failed to convert nonce to Nonce"))?,
+				transaction_type: value
+					.transaction_type
+					.ok_or(anyhow!("Missing transaction_type"))?
+					.try_into()?,
+				fee_limit: crate::Balance::from_str(&value.fee_limit)
+					.map_err(|_| anyhow!("Failed to convert fee_limit to Balance"))?,
+				signature: value.signature,
+				verifying_key: value.verifying_key,
+				eth_original_transaction: None,
+			})
+		}
+	}
+
 	impl TryFrom<estimate_fee_request::TransactionType> for super::transaction::TransactionTypeV2 {
 		type Error = anyhow::Error;
 
@@ -262,7 +316,7 @@ pub mod rpc_model {
 				estimate_fee_request::TransactionType::NativeTokenTransfer(
 					NativeTokenTransfer { address, amount },
 				) => super::transaction::TransactionTypeV2::NativeTokenTransfer(
-					address.try_into().map_err(|_| anyhow!("Failed to convert address bytes"))?,
+					address_field(&address, "native_token_transfer.address")?,
 					u128::from_str(&amount)
 						.map_err(|_| anyhow!("Failed to convert string to u128"))?,
 				),
@@ -285,7 +339,10 @@ pub mod rpc_model {
 				estimate_fee_request::TransactionType::SmartContractInit(
 					SmartContractInitV2 { contract_code_address, arguments, deposit },
 				) => super::transaction::TransactionTypeV2::SmartContractInit {
-					contract_code_address: contract_code_address.try_into().map_err(|_| anyhow!("Failed to convert address bytes"))?,
+					contract_code_address: address_field(
+						&contract_code_address,
+						"smart_contract_init.contract_code_address",
+					)?,
 					arguments,
 					deposit: u128::from_str(&deposit)
 							.map_err(|_| anyhow!("Failed to convert string to u128"))?,
@@ -294,7 +351,10 @@ pub mod rpc_model {
 				estimate_fee_request::TransactionType::SmartContractFunctionCall(
 					SmartContractFunctionCallV2 { contract_instance_address, function_name, arguments, deposit },
 				) => super::transaction::TransactionTypeV2::SmartContractFunctionCall {
-					contract_instance_address: contract_instance_address.try_into().map_err(|_| anyhow!("Failed to convert address bytes"))?,
+					contract_instance_address: address_field(
+						&contract_instance_address,
+						"smart_contract_function_call.contract_instance_address",
+					)?,
 					function: function_name,
 					arguments,
 					deposit: u128::from_str(&deposit)
@@ -311,25 +371,33 @@ pub mod rpc_model {
 					},
 				) => super::transaction::TransactionTypeV2::CreateStakingPool {
 					contract_instance_address: match contract_instance_address {
-						Some(x) => Some(x.try_into().map_err(|_| {
-							anyhow!("Failed to convert contract_instance_address bytes")
-						})?),
+						Some(x) => Some(address_field(
+							&x,
+							"create_staking_pool.contract_instance_address",
+						)?),
 						None => None,
 					},
 
-					min_stake: min_stake.and_then(|s| s.parse().ok()),
-					max_stake: max_stake.and_then(|s| s.parse().ok()),
-					min_pool_balance: min_pool_balance.and_then(|s| s.parse().ok()),
-					max_pool_balance: max_pool_balance.and_then(|s| s.parse().ok()),
-					staking_period: staking_period.and_then(|s| s.parse().ok()),
+					min_stake: optional_number_field(min_stake, "create_staking_pool.min_stake")?,
+					max_stake: optional_number_field(max_stake, "create_staking_pool.max_stake")?,
+					min_pool_balance: optional_number_field(
+						min_pool_balance,
+						"create_staking_pool.min_pool_balance",
+					)?,
+					max_pool_balance: optional_number_field(
+						max_pool_balance,
+						"create_staking_pool.max_pool_balance",
+					)?,
+					staking_period: optional_number_field(
+						staking_period,
+						"create_staking_pool.staking_period",
+					)?,
 				},
 				estimate_fee_request::TransactionType::Stake(Stake {
 					pool_address,
 					amount,
 				}) => super::transaction::TransactionTypeV2::Stake {
-					pool_address: pool_address
-						.try_into()
-						.map_err(|_| anyhow!("Failed to convert pool_address bytes"))?,
+					pool_address: address_field(&pool_address, "stake.pool_address")?,
 					amount: u128::from_str(&amount)
 						.map_err(|_| anyhow!("Failed to convert string to u128"))?,
 				},
@@ -337,9 +405,7 @@ pub mod rpc_model {
 					pool_address,
 					amount,
 				}) => super::transaction::TransactionTypeV2::UnStake {
-					pool_address: pool_address
-						.try_into()
-						.map_err(|_| anyhow!("Failed to convert pool_address bytes"))?,
+					pool_address: address_field(&pool_address, "unstake.pool_address")?,
 					amount: u128::from_str(&amount)
 						.map_err(|_| anyhow!("Failed to convert string to u128"))?,
 				},
@@ -357,7 +423,7 @@ pub mod rpc_model {
 					address,
 					amount,
 				}) => super::transaction::TransactionType::NativeTokenTransfer(
-					address.try_into().map_err(|_| anyhow!("Failed to convert address bytes"))?,
+					address_field(&address, "native_token_transfer.address")?,
 					u128::from_str(&amount)
 						.map_err(|_| anyhow!("Failed to convert string to u128"))?,
 				),
@@ -378,15 +444,16 @@ pub mod rpc_model {
 					address,
 					arguments,
 				}) => super::transaction::TransactionType::SmartContractInit(
-					address.try_into().map_err(|_| anyhow!("Failed to convert address bytes"))?,
+					address_field(&address, "smart_contract_init.address")?,
 					arguments,
 				),
 				transaction::Transaction::SmartContractFunctionCall(
 					SmartContractFunctionCall { contract_address, function_name, arguments },
 				) => super::transaction::TransactionType::SmartContractFunctionCall {
-					contract_instance_address: contract_address
-						.try_into()
-						.map_err(|_| anyhow!("Failed to convert contract_address bytes"))?,
+					contract_instance_address: address_field(
+						&contract_address,
+						"smart_contract_function_call.contract_address",
+					)?,
 					function: function_name,
 					arguments,
 				},
@@ -400,9 +467,10 @@ pub mod rpc_model {
 					staking_period,
 				}) => super::transaction::TransactionType::CreateStakingPool {
 					contract_instance_address: match contract_instance_address {
-						Some(x) => Some(x.try_into().map_err(|_| {
-							anyhow!("Failed to convert contract_instance_address bytes")
-						})?),
+						Some(x) => Some(address_field(
+							&x,
+							"create_staking_pool.contract_instance_address",
+						)?),
 						None => None,
 					},
 					min_stake: min_stake.map(|x| x.into()),
@@ -414,17 +482,13 @@ pub mod rpc_model {
 				*/
 				transaction::Transaction::Stake(Stake { pool_address, amount }) =>
 					super::transaction::TransactionType::Stake {
-						pool_address: pool_address
-							.try_into()
-							.map_err(|_| anyhow!("Failed to convert pool_address bytes"))?,
+						pool_address: address_field(&pool_address, "stake.pool_address")?,
 						amount: u128::from_str(&amount)
 							.map_err(|_| anyhow!("Failed to convert string to u128"))?,
 					},
 				transaction::Transaction::Unstake(UnStake { pool_address, amount }) =>
 					super::transaction::TransactionType::UnStake {
-						pool_address: pool_address
-							.try_into()
-							.map_err(|_| anyhow!("Failed to convert pool_address bytes"))?,
+						pool_address: address_field(&pool_address, "unstake.pool_address")?,
 						amount: u128::from_str(&amount)
 							.map_err(|_| anyhow!("Failed to convert string to u128"))?,
 					},
@@ -432,7 +496,449 @@ pub mod rpc_model {
 			Ok(result_txn_type)
 		}
 	}
-	
+
+	/// Why a transaction could not be converted between its proto messages and domain types.
+	/// Fields are named by their proto path.
+	#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+	pub enum ConversionError {
+		#[error("missing field `{0}`")]
+		MissingField(&'static str),
+		#[error(transparent)]
+		Address(#[from] AddressError),
+		#[error("invalid number {value:?} in field `{field}`")]
+		InvalidNumber { field: &'static str, value: String },
+		#[error("invalid value {value} for enum field `{field}`")]
+		InvalidEnum { field: &'static str, value: i32 },
+		#[error("invalid length {len} for field `{field}`, expected 32 bytes")]
+		InvalidHashLength { field: &'static str, len: usize },
+		#[error("tx_type {tx_type:?} does not match the {transaction} transaction")]
+		TxTypeMismatch { tx_type: TransactionType, transaction: &'static str },
+		#[error("{transaction} transactions cannot be represented in `{message}`")]
+		Unrepresentable { transaction: &'static str, message: &'static str },
+	}
+
+	fn number_field<T: FromStr>(value: &str, field: &'static str) -> Result<T, ConversionError> {
+		value
+			.parse()
+			.map_err(|_| ConversionError::InvalidNumber { field, value: value.to_owned() })
+	}
+
+	/// Rejects a set value that does not parse, rather than treating it as unset.
+	fn optional_number_field<T: FromStr>(
+		value: Option<String>,
+		field: &'static str,
+	) -> Result<Option<T>, ConversionError> {
+		value.map(|value| number_field(&value, field)).transpose()
+	}
+
+	fn hash_field(bytes: &[u8], field: &'static str) -> Result<[u8; 32], ConversionError> {
+		bytes
+			.try_into()
+			.map_err(|_| ConversionError::InvalidHashLength { field, len: bytes.len() })
+	}
+
+	/// Proto versions count from 0, the domain versions from 1.
+	impl From<TransactionVersion> for super::transaction::TransactionVersion {
+		fn from(value: TransactionVersion) -> Self {
+			match value {
+				TransactionVersion::V1 => Self::V1,
+				TransactionVersion::V2 => Self::V2,
+				TransactionVersion::V3 => Self::V3,
+			}
+		}
+	}
+
+	impl TryFrom<transaction_v3::Transaction> for super::transaction::TransactionTypeV2 {
+		type Error = ConversionError;
+
+		fn try_from(value: transaction_v3::Transaction) -> Result<Self, Self::Error> {
+			use super::transaction::TransactionTypeV2 as Domain;
+
+			Ok(match value {
+				transaction_v3::Transaction::NativeTokenTransfer(NativeTokenTransfer {
+					address,
+					amount,
+				}) => Domain::NativeTokenTransfer(
+					address_field(&address, "native_token_transfer.address")?,
+					number_field(&amount, "native_token_transfer.amount")?,
+				),
+				transaction_v3::Transaction::SmartContractDeployment(SmartContractDeploymentV2 {
+					access_type,
+					contract_type,
+					contract_code,
+					deposit,
+					salt,
+				}) => Domain::SmartContractDeployment {
+					access_type: access_type.try_into().map_err(|_| {
+						ConversionError::InvalidEnum {
+							field: "smart_contract_deployment.access_type",
+							value: access_type,
+						}
+					})?,
+					contract_type: contract_type.try_into().map_err(|_| {
+						ConversionError::InvalidEnum {
+							field: "smart_contract_deployment.contract_type",
+							value: contract_type,
+						}
+					})?,
+					contract_code,
+					deposit: number_field(&deposit, "smart_contract_deployment.deposit")?,
+					salt,
+				},
+				transaction_v3::Transaction::SmartContractInit(SmartContractInitV2 {
+					contract_code_address,
+					arguments,
+					deposit,
+				}) => Domain::SmartContractInit {
+					contract_code_address: address_field(
+						&contract_code_address,
+						"smart_contract_init.contract_code_address",
+					)?,
+					arguments,
+					deposit: number_field(&deposit, "smart_contract_init.deposit")?,
+				},
+				transaction_v3::Transaction::SmartContractFunctionCall(
+					SmartContractFunctionCallV2 {
+						contract_instance_address,
+						function_name,
+						arguments,
+						deposit,
+					},
+				) => Domain::SmartContractFunctionCall {
+					contract_instance_address: address_field(
+						&contract_instance_address,
+						"smart_contract_function_call.contract_instance_address",
+					)?,
+					function: function_name,
+					arguments,
+					deposit: number_field(&deposit, "smart_contract_function_call.deposit")?,
+				},
+				transaction_v3::Transaction::Stake(Stake { pool_address, amount }) =>
+					Domain::Stake {
+						pool_address: address_field(&pool_address, "stake.pool_address")?,
+						amount: number_field(&amount, "stake.amount")?,
+					},
+				transaction_v3::Transaction::Unstake(UnStake { pool_address, amount }) =>
+					Domain::UnStake {
+						pool_address: address_field(&pool_address, "unstake.pool_address")?,
+						amount: number_field(&amount, "unstake.amount")?,
+					},
+			})
+		}
+	}
+
+	impl transaction_v3::Transaction {
+		/// The `tx_type` a node sets alongside this transaction.
+		pub fn tx_type(&self) -> TransactionType {
+			match self {
+				Self::NativeTokenTransfer(_) => TransactionType::NativeTokenTransfer,
+				Self::SmartContractDeployment(_) => TransactionType::SmartContractDeployment,
+				Self::SmartContractInit(_) => TransactionType::SmartContractInstantiation,
+				Self::SmartContractFunctionCall(_) => TransactionType::SmartContractFunctionCall,
+				Self::Stake(_) => TransactionType::Stake,
+				Self::Unstake(_) => TransactionType::Unstake,
+			}
+		}
+	}
+
+	/// Checks `tx_type` against the transaction it describes, an unspecified `tx_type` matches any
+	/// transaction.
+	impl TryFrom<TransactionV3> for super::transaction::TransactionV3 {
+		type Error = ConversionError;
+
+		fn try_from(value: TransactionV3) -> Result<Self, Self::Error> {
+			let version = TransactionVersion::from_i32(value.version)
+				.ok_or(ConversionError::InvalidEnum { field: "version", value: value.version })?;
+			let tx_type = TransactionType::from_i32(value.tx_type)
+				.ok_or(ConversionError::InvalidEnum { field: "tx_type", value: value.tx_type })?;
+			let transaction = value.transaction.ok_or(ConversionError::MissingField("transaction"))?;
+			if tx_type != TransactionType::Unspecified && tx_type != transaction.tx_type() {
+				return Err(ConversionError::TxTypeMismatch {
+					tx_type,
+					transaction: transaction.tx_type().as_str_name(),
+				})
+			}
+
+			Ok(Self {
+				version: version.into(),
+				nonce: number_field(&value.nonce, "nonce")?,
+				transaction_type: transaction.try_into()?,
+				fee_limit: number_field(&value.fee_limit, "fee_limit")?,
+				signature: value.signature,
+				verifying_key: value.verifying_key,
+				eth_original_transaction: value.eth_original_transaction,
+			})
+		}
+	}
+
+	impl TryFrom<TransactionV3Response> for super::transaction::TransactionV3Response {
+		type Error = ConversionError;
+
+		fn try_from(value: TransactionV3Response) -> Result<Self, Self::Error> {
+			Ok(Self {
+				transaction: value
+					.transaction
+					.ok_or(ConversionError::MissingField("transaction"))?
+					.try_into()?,
+				from: address_field(&value.from, "from")?,
+				transaction_hash: hash_field(&value.transaction_hash, "transaction_hash")?,
+				block_hash: hash_field(&value.block_hash, "block_hash")?,
+				block_number: value.block_number.try_into().map_err(|_| {
+					ConversionError::InvalidNumber {
+						field: "block_number",
+						value: value.block_number.to_string(),
+					}
+				})?,
+				fee_used: number_field(&value.fee_used, "fee_used")?,
+				timestamp: value.timestamp.into(),
+			})
+		}
+	}
+
+	impl From<super::transaction::TransactionVersion> for TransactionVersion {
+		fn from(value: super::transaction::TransactionVersion) -> Self {
+			match value {
+				super::transaction::TransactionVersion::V1 => Self::V1,
+				super::transaction::TransactionVersion::V2 => Self::V2,
+				super::transaction::TransactionVersion::V3 => Self::V3,
+			}
+		}
+	}
+
+	fn transaction_name(value: &super::transaction::TransactionTypeV2) -> &'static str {
+		use super::transaction::TransactionTypeV2 as Domain;
+
+		match value {
+			Domain::NativeTokenTransfer(..) => "NativeTokenTransfer",
+			Domain::SmartContractDeployment { .. } => "SmartContractDeployment",
+			Domain::SmartContractInit { .. } => "SmartContractInit",
+			Domain::SmartContractFunctionCall { .. } => "SmartContractFunctionCall",
+			Domain::CreateStakingPool { .. } => "CreateStakingPool",
+			Domain::Stake { .. } => "Stake",
+			Domain::UnStake { .. } => "UnStake",
+			Domain::StakingPoolContract { .. } => "StakingPoolContract",
+		}
+	}
+
+	/// The request arm of `value`, shared by `SubmitTransactionRequestV2` and
+	/// `EstimateFeeRequest`, which is named `message` in the error.
+	fn request_transaction(
+		value: super::transaction::TransactionTypeV2,
+		message: &'static str,
+	) -> Result<submit_transaction_request_v2::TransactionType, ConversionError> {
+		use super::transaction::TransactionTypeV2 as Domain;
+		use submit_transaction_request_v2::TransactionType as Proto;
+
+		Ok(match value {
+			Domain::NativeTokenTransfer(address, amount) =>
+				Proto::NativeTokenTransfer(NativeTokenTransfer {
+					address: address.to_vec(),
+					amount: amount.to_string(),
+				}),
+			Domain::SmartContractDeployment {
+				access_type,
+				contract_type,
+				contract_code,
+				deposit,
+				salt,
+			} => Proto::SmartContractDeployment(SmartContractDeploymentV2 {
+				access_type: access_type as i32,
+				contract_type: contract_type as i32,
+				contract_code,
+				deposit: deposit.to_string(),
+				salt,
+			}),
+			Domain::SmartContractInit { contract_code_address, arguments, deposit } =>
+				Proto::SmartContractInit(SmartContractInitV2 {
+					contract_code_address: contract_code_address.to_vec(),
+					arguments,
+					deposit: deposit.to_string(),
+				}),
+			Domain::SmartContractFunctionCall {
+				contract_instance_address,
+				function,
+				arguments,
+				deposit,
+			} => Proto::SmartContractFunctionCall(SmartContractFunctionCallV2 {
+				contract_instance_address: contract_instance_address.to_vec(),
+				function_name: function,
+				arguments,
+				deposit: deposit.to_string(),
+			}),
+			Domain::CreateStakingPool {
+				contract_instance_address,
+				min_stake,
+				max_stake,
+				min_pool_balance,
+				max_pool_balance,
+				staking_period,
+			} => Proto::CreateStakingPool(CreateStakingPool {
+				contract_instance_address: contract_instance_address.map(|x| x.to_vec()),
+				min_stake: min_stake.map(|x| x.to_string()),
+				max_stake: max_stake.map(|x| x.to_string()),
+				min_pool_balance: min_pool_balance.map(|x| x.to_string()),
+				max_pool_balance: max_pool_balance.map(|x| x.to_string()),
+				staking_period: staking_period.map(|x| x.to_string()),
+			}),
+			Domain::Stake { pool_address, amount } =>
+				Proto::Stake(Stake { pool_address: pool_address.to_vec(), amount: amount.to_string() }),
+			Domain::UnStake { pool_address, amount } => Proto::Unstake(UnStake {
+				pool_address: pool_address.to_vec(),
+				amount: amount.to_string(),
+			}),
+			value @ Domain::StakingPoolContract { .. } =>
+				return Err(ConversionError::Unrepresentable {
+					transaction: transaction_name(&value),
+					message,
+				}),
+		})
+	}
+
+	impl TryFrom<super::transaction::TransactionTypeV2> for submit_transaction_request_v2::TransactionType {
+		type Error = ConversionError;
+
+		fn try_from(value: super::transaction::TransactionTypeV2) -> Result<Self, Self::Error> {
+			request_transaction(value, "SubmitTransactionRequestV2")
+		}
+	}
+
+	impl TryFrom<super::transaction::TransactionTypeV2> for estimate_fee_request::TransactionType {
+		type Error = ConversionError;
+
+		fn try_from(value: super::transaction::TransactionTypeV2) -> Result<Self, Self::Error> {
+			request_transaction(value, "EstimateFeeRequest").map(Into::into)
+		}
+	}
+
+	impl From<submit_transaction_request_v2::TransactionType> for estimate_fee_request::TransactionType {
+		fn from(value: submit_transaction_request_v2::TransactionType) -> Self {
+			use submit_transaction_request_v2::TransactionType as Submit;
+
+			match value {
+				Submit::NativeTokenTransfer(v) => Self::NativeTokenTransfer(v),
+				Submit::SmartContractDeployment(v) => Self::SmartContractDeployment(v),
+				Submit::SmartContractInit(v) => Self::SmartContractInit(v),
+				Submit::SmartContractFunctionCall(v) => Self::SmartContractFunctionCall(v),
+				Submit::CreateStakingPool(v) => Self::CreateStakingPool(v),
+				Submit::Stake(v) => Self::Stake(v),
+				Submit::Unstake(v) => Self::Unstake(v),
+			}
+		}
+	}
+
+	impl TryFrom<super::transaction::TransactionTypeV2> for transaction_v3::Transaction {
+		type Error = ConversionError;
+
+		fn try_from(value: super::transaction::TransactionTypeV2) -> Result<Self, Self::Error> {
+			use submit_transaction_request_v2::TransactionType as Submit;
+
+			let transaction = transaction_name(&value);
+			Ok(match request_transaction(value, "TransactionV3")? {
+				Submit::NativeTokenTransfer(v) => Self::NativeTokenTransfer(v),
+				Submit::SmartContractDeployment(v) => Self::SmartContractDeployment(v),
+				Submit::SmartContractInit(v) => Self::SmartContractInit(v),
+				Submit::SmartContractFunctionCall(v) => Self::SmartContractFunctionCall(v),
+				Submit::Stake(v) => Self::Stake(v),
+				Submit::Unstake(v) => Self::Unstake(v),
+				Submit::CreateStakingPool(_) =>
+					return Err(ConversionError::Unrepresentable {
+						transaction,
+						message: "TransactionV3",
+					}),
+			})
+		}
+	}
+
+	impl TryFrom<super::transaction::TransactionV3> for TransactionV3 {
+		type Error = ConversionError;
+
+		fn try_from(value: super::transaction::TransactionV3) -> Result<Self, Self::Error> {
+			let transaction = transaction_v3::Transaction::try_from(value.transaction_type)?;
+			Ok(Self {
+				version: TransactionVersion::from(value.version) as i32,
+				tx_type: transaction.tx_type() as i32,
+				transaction: Some(transaction),
+				nonce: value.nonce.to_string(),
+				fee_limit: value.fee_limit.to_string(),
+				signature: value.signature,
+				verifying_key: value.verifying_key,
+				eth_original_transaction: value.eth_original_transaction,
+			})
+		}
+	}
+
+	/// Parses the decimal strings of a sign payload back into balances.
+	impl TryFrom<super::TransactionTypeV2SignPayload> for super::transaction::TransactionTypeV2 {
+		type Error = ConversionError;
+
+		fn try_from(value: super::TransactionTypeV2SignPayload) -> Result<Self, Self::Error> {
+			use super::TransactionTypeV2SignPayload as Payload;
+
+			Ok(match value {
+				Payload::NativeTokenTransfer(address, amount) => Self::NativeTokenTransfer(
+					address,
+					number_field(&amount, "native_token_transfer.amount")?,
+				),
+				Payload::SmartContractDeployment {
+					access_type,
+					contract_type,
+					contract_code,
+					deposit,
+					salt,
+				} => Self::SmartContractDeployment {
+					access_type,
+					contract_type,
+					contract_code,
+					deposit: number_field(&deposit, "smart_contract_deployment.deposit")?,
+					salt,
+				},
+				Payload::SmartContractInit { contract_code_address, arguments, deposit } =>
+					Self::SmartContractInit {
+						contract_code_address,
+						arguments,
+						deposit: number_field(&deposit, "smart_contract_init.deposit")?,
+					},
+				Payload::SmartContractFunctionCall {
+					contract_instance_address,
+					function,
+					arguments,
+					deposit,
+				} => Self::SmartContractFunctionCall {
+					contract_instance_address,
+					function,
+					arguments,
+					deposit: number_field(&deposit, "smart_contract_function_call.deposit")?,
+				},
+				Payload::CreateStakingPool {
+					contract_instance_address,
+					min_stake,
+					max_stake,
+					min_pool_balance,
+					max_pool_balance,
+					staking_period,
+				} => Self::CreateStakingPool {
+					contract_instance_address,
+					min_stake,
+					max_stake,
+					min_pool_balance,
+					max_pool_balance,
+					staking_period,
+				},
+				Payload::Stake { pool_address, amount } => Self::Stake { pool_address, amount },
+				Payload::UnStake { pool_address, amount } => Self::UnStake { pool_address, amount },
+				Payload::StakingPoolContract { pool_address, contract_instance_address } =>
+					Self::StakingPoolContract { pool_address, contract_instance_address },
+			})
+		}
+	}
+
+	impl TryFrom<super::TransactionTypeV2SignPayload> for submit_transaction_request_v2::TransactionType {
+		type Error = ConversionError;
+
+		fn try_from(value: super::TransactionTypeV2SignPayload) -> Result<Self, Self::Error> {
+			super::transaction::TransactionTypeV2::try_from(value)?.try_into()
+		}
+	}
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -442,14 +948,14 @@ pub struct TXSignPayload {
 	pub fee_limit: Balance,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TXSignPayloadV2 {
 	pub nonce: String,
 	pub transaction_type: TransactionTypeV2SignPayload,
 	pub fee_limit: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TransactionTypeV2SignPayload {
 	NativeTokenTransfer(Address, String),
 	SmartContractDeployment {
@@ -514,55 +1020,169 @@ impl From<TransactionTypeV2> for TransactionTypeV2SignPayload {
 	}
 }
 
-pub fn sign(
-	secret_key: SecretKey,
+/// Reasons a signed transaction request fails verification.
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyError {
+	#[error("invalid verifying key: {0}")]
+	InvalidVerifyingKey(crypto::Error),
+	#[error("invalid signature encoding: {0}")]
+	InvalidSignature(crypto::Error),
+	#[error("failed to build sign payload: {0}")]
+	Payload(anyhow::Error),
+	#[error("signature does not match the sign payload")]
+	SignatureMismatch,
+}
+
+impl TXSignPayload {
+	/// Canonical bytes signed by [`sign`], see [`canonical`].
+	pub fn signing_bytes(&self) -> Vec<u8> {
+		canonical::encode_sign_payload(self)
+	}
+
+	/// SHA-256 digest of [`Self::signing_bytes`].
+	pub fn digest(&self) -> [u8; 32] {
+		Sha256::digest(self.signing_bytes()).into()
+	}
+}
+
+impl TXSignPayloadV2 {
+	/// Canonical bytes signed by [`sign_v2`], see [`canonical`].
+	pub fn signing_bytes(&self) -> Vec<u8> {
+		canonical::encode_sign_payload_v2(self)
+	}
+
+	/// SHA-256 digest of [`Self::signing_bytes`].
+	pub fn digest(&self) -> [u8; 32] {
+		Sha256::digest(self.signing_bytes()).into()
+	}
+}
+
+pub(crate) fn verify_message(
+	digest: &[u8; 32],
+	signature: &[u8],
+	verifying_key: &[u8],
+) -> Result<(), VerifyError> {
+	let public_key =
+		crypto::PublicKey::from_slice(verifying_key).map_err(VerifyError::InvalidVerifyingKey)?;
+	public_key.verify_digest(digest, signature).map_err(|e| match e {
+		crypto::Error::IncorrectSignature => VerifyError::SignatureMismatch,
+		e => VerifyError::InvalidSignature(e),
+	})
+}
+
+pub async fn sign(
+	signer: &dyn Signer,
 	transaction_type: rpc_model::submit_transaction_request::TransactionType,
 	fee_limit: Balance,
 	nonce: Nonce,
 ) -> Result<Vec<u8>> {
 	let transaction_type: TransactionType = transaction_type.try_into()?;
 	let sign_payload = TXSignPayload { nonce, transaction_type, fee_limit };
-	let json_str = serde_json::to_string(&sign_payload)?;
-	let message = Message::from_hashed_data::<sha256::Hash>(json_str.as_bytes());
-	let sig = secret_key.sign_ecdsa(message);
-	Ok(sig.serialize_compact().to_vec())
+	Ok(signer.sign_digest(&sign_payload.digest()).await?.signature)
 }
 
-pub fn sign_v2(
-	secret_key: SecretKey,
+pub async fn sign_v2(
+	signer: &dyn Signer,
 	transaction_type: rpc_model::submit_transaction_request_v2::TransactionType,
 	fee_limit: Balance,
 	nonce: Nonce,
 ) -> Result<Vec<u8>> {
 	let transaction_type: TransactionTypeV2 = transaction_type.try_into()?;
 	let sign_payload = TXSignPayloadV2 { nonce: nonce.to_string(), transaction_type: transaction_type.into(), fee_limit: fee_limit.to_string() };
-	let json_str = serde_json::to_string(&sign_payload)?;
-	let message = Message::from_hashed_data::<sha256::Hash>(json_str.as_bytes());
-	let sig = secret_key.sign_ecdsa(message);
-	Ok(sig.serialize_compact().to_vec())
+	Ok(signer.sign_digest(&sign_payload.digest()).await?.signature)
+}
+
+/// Like [`sign_v2`], but returns the 65-byte `r || s || v` signature, `v` being the recovery id 0
+/// or 1, so the signer can be recovered with [`recover_address`].
+pub async fn sign_v2_recoverable(
+	signer: &dyn Signer,
+	transaction_type: rpc_model::submit_transaction_request_v2::TransactionType,
+	fee_limit: Balance,
+	nonce: Nonce,
+) -> Result<[u8; 65]> {
+	let transaction_type: TransactionTypeV2 = transaction_type.try_into()?;
+	let sign_payload = TXSignPayloadV2 {
+		nonce: nonce.to_string(),
+		transaction_type: transaction_type.into(),
+		fee_limit: fee_limit.to_string(),
+	};
+	signer::sign_digest_recoverable(signer, &sign_payload.digest()).await
+}
+
+/// Recovers the address that signed `payload` from a 65-byte recoverable signature, derived the
+/// same way as [`get_address_from_private_key`].
+pub fn recover_address(payload: &TXSignPayloadV2, signature: &[u8]) -> Result<address::Address> {
+	let public_key = crypto::PublicKey::recover_from_digest(&payload.digest(), signature)?;
+	address_from_verifying_key(&public_key.serialize())
+}
+
+/// Rebuilds the `TXSignPayload` that `request` was signed over.
+pub fn sign_payload(request: &rpc_model::SubmitTransactionRequest) -> Result<TXSignPayload> {
+	let transaction_type: TransactionType = request
+		.transaction_type
+		.clone()
+		.ok_or(anyhow!("missing transaction_type"))?
+		.try_into()?;
+	let nonce = request.nonce.parse().map_err(|_| anyhow!("Failed to parse nonce"))?;
+	let fee_limit = request.fee_limit.parse().map_err(|_| anyhow!("Failed to parse fee_limit"))?;
+	Ok(TXSignPayload { nonce, transaction_type, fee_limit })
+}
+
+/// Rebuilds the `TXSignPayloadV2` that `request` was signed over.
+pub fn sign_payload_v2(request: &rpc_model::SubmitTransactionRequestV2) -> Result<TXSignPayloadV2> {
+	let transaction_type: TransactionTypeV2 = request
+		.transaction_type
+		.clone()
+		.ok_or(anyhow!("missing transaction_type"))?
+		.try_into()?;
+	let nonce: Nonce = request.nonce.parse().map_err(|_| anyhow!("Failed to parse nonce"))?;
+	let fee_limit: Balance =
+		request.fee_limit.parse().map_err(|_| anyhow!("Failed to parse fee_limit"))?;
+	Ok(TXSignPayloadV2 {
+		nonce: nonce.to_string(),
+		transaction_type: transaction_type.into(),
+		fee_limit: fee_limit.to_string(),
+	})
+}
+
+/// Rebuilds the `TXSignPayload` of `request` and checks its signature against its verifying key.
+pub fn verify(request: &rpc_model::SubmitTransactionRequest) -> Result<(), VerifyError> {
+	let sign_payload = sign_payload(request).map_err(VerifyError::Payload)?;
+	verify_message(&sign_payload.digest(), &request.signature, &request.verifying_key)
+}
+
+/// Rebuilds the `TXSignPayloadV2` of `request` and checks its signature against its verifying key.
+pub fn verify_v2(request: &rpc_model::SubmitTransactionRequestV2) -> Result<(), VerifyError> {
+	let sign_payload = sign_payload_v2(request).map_err(VerifyError::Payload)?;
+	verify_message(&sign_payload.digest(), &request.signature, &request.verifying_key)
 }
 
 pub fn get_address_from_privkey_str(private_key: &str) -> Result<String> {
-	let private_key = SecretKey::from_slice(&hex::decode(private_key)?)?;
-	get_address_from_private_key(&private_key)
+	get_address_from_private_key(&SecretKey::from_hex(private_key)?)
 }
 
 pub fn get_address_from_private_key(private_key: &SecretKey) -> Result<String> {
-	let verifying_key_bytes = private_key.public_key(&Secp256k1::new()).serialize().to_vec();
+	get_address_from_verifying_key(&private_key.public_key().serialize())
+}
+
+pub fn get_address_from_verifying_key(verifying_key_bytes: &[u8]) -> Result<String> {
+	Ok(address_from_verifying_key(verifying_key_bytes)?.to_string())
+}
 
-	let public_key = match secp256k1::PublicKey::from_slice(verifying_key_bytes.as_slice()) {
+pub fn address_from_verifying_key(verifying_key_bytes: &[u8]) -> Result<address::Address> {
+	let public_key = match crypto::PublicKey::from_slice(verifying_key_bytes) {
 		Ok(public_key) => public_key,
 		Err(err) => return Err(anyhow!("Unable to construct public key {:?}", err)),
 	};
+	Ok(address_from_public_key(&public_key))
+}
 
-	let k_pub_bytes = K256PublicKey::from_sec1_bytes(&public_key.serialize_uncompressed()).unwrap();
-
-	let k_pub_bytes = k_pub_bytes.to_encoded_point(false);
-	let k_pub_bytes = k_pub_bytes.as_bytes();
+pub(crate) fn address_from_public_key(public_key: &crypto::PublicKey) -> address::Address {
+	let k_pub_bytes = public_key.serialize_uncompressed();
 
 	let hash = Keccak256::digest(&k_pub_bytes[1..]);
 	let mut bytes = [0u8; 20];
 	bytes.copy_from_slice(&hash[12..]);
 
-	Ok(hex::encode(bytes))
+	address::Address(bytes)
 }
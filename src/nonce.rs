@@ -0,0 +1,229 @@
+//! Nonce allocation for accounts that send several transactions at once.
+//!
+//! ```ignore
+//! let nonces = Arc::new(NonceManager::new(client.clone()));
+//! let nonce = nonces.next(address).await?;
+//! match client.submit_transaction_v2(request).await {
+//!     Ok(_) => nonces.confirm(address, nonce),
+//!     Err(_) => {
+//!         nonces.reject(address, nonce);
+//!         nonces.resync(address).await?;
+//!     },
+//! }
+//! ```
+
+use crate::{address::Address, json, primitives::Nonce};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::{
+	collections::{hash_map::Entry, BTreeSet, HashMap},
+	sync::Mutex,
+};
+#[cfg(feature = "grpc")]
+use {
+	crate::rpc_model::{node_client::NodeClient, GetCurrentNonceRequest},
+	anyhow::Context,
+	tonic::transport::Channel,
+};
+
+/// Where a [`NonceManager`] reads an account's nonce from.
+#[async_trait]
+pub trait NonceSource: Send + Sync {
+	/// The nonce of the account's latest transaction on chain.
+	async fn current_nonce(&self, address: Address) -> Result<Nonce>;
+}
+
+/// Reads the nonce with the `GetCurrentNonce` RPC.
+#[cfg(feature = "grpc")]
+#[async_trait]
+impl NonceSource for NodeClient<Channel> {
+	async fn current_nonce(&self, address: Address) -> Result<Nonce> {
+		let response = self
+			.clone()
+			.get_current_nonce(GetCurrentNonceRequest { address: address.to_string() })
+			.await?
+			.into_inner();
+		response.nonce.parse().context("failed to parse nonce")
+	}
+}
+
+/// Reads the nonce from the account state over JSON-RPC.
+#[derive(Debug, Clone)]
+pub struct JsonRpcNonceSource {
+	client: reqwest::Client,
+	url: String,
+}
+
+impl JsonRpcNonceSource {
+	pub fn new(client: reqwest::Client, url: impl Into<String>) -> Self {
+		Self { client, url: url.into() }
+	}
+}
+
+#[async_trait]
+impl NonceSource for JsonRpcNonceSource {
+	async fn current_nonce(&self, address: Address) -> Result<Nonce> {
+		json::get_account_nonce(self.client.post(&self.url), address.to_string()).await
+	}
+}
+
+/// The state of an account's nonces after a [`NonceManager::resync`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonceSync {
+	/// The nonce of the account's latest transaction on chain.
+	pub current: Nonce,
+	/// The nonce the next new transaction gets when there are no gaps.
+	pub next: Nonce,
+	/// Nonces of rejected or dropped transactions that later transactions wait on. They are
+	/// handed out again, lowest first, before any new nonce.
+	pub gaps: Vec<Nonce>,
+}
+
+#[derive(Debug, Default)]
+struct AccountNonces {
+	next: Nonce,
+	/// Handed out and not yet confirmed, rejected or seen on chain.
+	pending: BTreeSet<Nonce>,
+	/// Confirmed and not yet seen on chain.
+	submitted: BTreeSet<Nonce>,
+	gaps: BTreeSet<Nonce>,
+}
+
+impl AccountNonces {
+	fn new(current: Nonce) -> Result<Self> {
+		Ok(Self { next: next_nonce(current)?, ..Self::default() })
+	}
+
+	fn allocate(&mut self) -> Result<Nonce> {
+		let nonce = match self.gaps.pop_first() {
+			Some(gap) => gap,
+			None => {
+				let nonce = self.next;
+				self.next = next_nonce(nonce)?;
+				nonce
+			},
+		};
+		self.pending.insert(nonce);
+		Ok(nonce)
+	}
+
+	fn reject(&mut self, nonce: Nonce) {
+		if !self.pending.remove(&nonce) && !self.submitted.remove(&nonce) {
+			return
+		}
+		self.gaps.insert(nonce);
+		// Nonces with nothing handed out after them are no gap and are simply reused.
+		while let Some(&last) = self.gaps.last() {
+			if self.pending.last().is_some_and(|&pending| pending > last) || last + 1 != self.next {
+				break
+			}
+			self.gaps.pop_last();
+			self.next = last;
+		}
+	}
+
+	fn confirm(&mut self, nonce: Nonce) {
+		if self.pending.remove(&nonce) {
+			self.submitted.insert(nonce);
+		}
+	}
+
+	/// Forgets the nonces up to `current` and turns every later one that is not pending into a
+	/// gap, including submitted transactions the chain has not included.
+	fn sync(&mut self, current: Nonce) -> Result<()> {
+		self.pending.retain(|&nonce| nonce > current);
+		self.submitted.clear();
+		if self.next <= current {
+			self.next = next_nonce(current)?;
+		}
+		self.gaps = (next_nonce(current)?..self.next)
+			.filter(|nonce| !self.pending.contains(nonce))
+			.collect();
+		Ok(())
+	}
+}
+
+fn next_nonce(nonce: Nonce) -> Result<Nonce> {
+	nonce.checked_add(1).ok_or(anyhow!("Nonce overflow"))
+}
+
+/// Hands out increasing nonces per account to concurrent tasks, reading each account's nonce
+/// from the node once and resyncing on request.
+///
+/// The manager assumes it is the only sender for the accounts it manages; nonces used by other
+/// senders are only noticed on [`NonceManager::resync`].
+pub struct NonceManager {
+	source: Box<dyn NonceSource>,
+	accounts: Mutex<HashMap<Address, AccountNonces>>,
+}
+
+impl NonceManager {
+	pub fn new(source: impl NonceSource + 'static) -> Self {
+		Self { source: Box::new(source), accounts: Mutex::new(HashMap::new()) }
+	}
+
+	/// The nonce for a new transaction from `address`. Concurrent callers get distinct nonces;
+	/// the nonce is fetched from the node only on the account's first use.
+	pub async fn next(&self, address: Address) -> Result<Nonce> {
+		if let Some(account) = self.lock().get_mut(&address) {
+			return account.allocate()
+		}
+		let account = AccountNonces::new(self.source.current_nonce(address).await?)?;
+		// Another task may have fetched the nonce meanwhile; the first one stays.
+		self.lock().entry(address).or_insert(account).allocate()
+	}
+
+	/// Marks `nonce` as accepted by the node. It is tracked as submitted until a
+	/// [`NonceManager::resync`] finds it on chain or reports it as dropped.
+	pub fn confirm(&self, address: Address, nonce: Nonce) {
+		if let Some(account) = self.lock().get_mut(&address) {
+			account.confirm(nonce);
+		}
+	}
+
+	/// Marks the pending or submitted transaction with `nonce` as rejected by the node or
+	/// dropped before inclusion, so the nonce is handed out again.
+	pub fn reject(&self, address: Address, nonce: Nonce) {
+		if let Some(account) = self.lock().get_mut(&address) {
+			account.reject(nonce);
+		}
+	}
+
+	/// Nonces of rejected or dropped transactions that later transactions from `address` wait
+	/// on, lowest first.
+	pub fn gaps(&self, address: Address) -> Vec<Nonce> {
+		self.lock()
+			.get(&address)
+			.map(|account| account.gaps.iter().copied().collect())
+			.unwrap_or_default()
+	}
+
+	/// Nonces of transactions from `address` the node accepted since the last resync, lowest
+	/// first.
+	pub fn submitted(&self, address: Address) -> Vec<Nonce> {
+		self.lock()
+			.get(&address)
+			.map(|account| account.submitted.iter().copied().collect())
+			.unwrap_or_default()
+	}
+
+	/// Reads the account's nonce from the node again, e.g. after the node rejected a nonce as
+	/// already used or a submitted transaction was dropped. Nonces up to the chain's are
+	/// forgotten and new ones start after it. Every later nonce that is not pending becomes a
+	/// gap, so submitted transactions the chain has not included yet are taken as dropped.
+	pub async fn resync(&self, address: Address) -> Result<NonceSync> {
+		let current = self.source.current_nonce(address).await?;
+		let mut accounts = self.lock();
+		let account = match accounts.entry(address) {
+			Entry::Occupied(entry) => entry.into_mut(),
+			Entry::Vacant(entry) => entry.insert(AccountNonces::new(current)?),
+		};
+		account.sync(current)?;
+		Ok(NonceSync { current, next: account.next, gaps: account.gaps.iter().copied().collect() })
+	}
+
+	fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<Address, AccountNonces>> {
+		// The map is left consistent by every critical section, so a poisoned lock is usable.
+		self.accounts.lock().unwrap_or_else(|e| e.into_inner())
+	}
+}
@@ -0,0 +1,300 @@
+//! Portable transactions for signing on an offline machine.
+//!
+//! An [`OfflineTransaction`] carries a `TXSignPayloadV2` with the nonce and fee limit already
+//! filled in, the verifying key of the account expected to sign it and the payload's digest. It
+//! moves between machines as JSON or as compact binary:
+//!
+//! 1. online: [`crate::builder::TransactionBuilder::prepare_with_grpc`] creates it, and the
+//!    operator notes its digest
+//! 2. offline: [`OfflineTransaction::sign`] checks the payload against the digest the operator
+//!    copied from the online machine and that the signer holds the expected key, and adds the
+//!    signature
+//! 3. online: [`OfflineTransaction::into_request`] checks the signature and returns the
+//!    `SubmitTransactionRequestV2` to broadcast
+//!
+//! The digest carried in the transaction only detects accidental changes. The copy the operator
+//! brings over separately detects deliberate ones; after signing, the signature does.
+
+use crate::{
+	address::Address,
+	primitives::*,
+	rpc_model::{submit_transaction_request_v2, ConversionError, SubmitTransactionRequestV2},
+	signer::Signer,
+	transaction::TransactionTypeV2,
+	TXSignPayloadV2, VerifyError,
+};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Version of the offline transaction format written by this crate.
+pub const OFFLINE_FORMAT_VERSION: u32 = 1;
+
+/// Leading bytes of the binary format.
+const MAGIC: &[u8; 4] = b"L1XO";
+
+#[derive(Debug, thiserror::Error)]
+pub enum OfflineError {
+	#[error("unsupported offline transaction version {0}")]
+	UnsupportedVersion(u32),
+	#[error("malformed offline transaction: {0}")]
+	Json(#[from] serde_json::Error),
+	#[error("malformed offline transaction: {0}")]
+	Binary(#[from] prost::DecodeError),
+	#[error("malformed offline transaction: {0}")]
+	Malformed(anyhow::Error),
+	#[error("not an offline transaction")]
+	BadMagic,
+	#[error("invalid transaction: {0}")]
+	InvalidTransaction(#[from] ConversionError),
+	#[error("invalid {field} {value:?}")]
+	InvalidNumber { field: &'static str, value: String },
+	#[error("payload is not in canonical form, its signature would not verify")]
+	NonCanonical,
+	#[error("invalid verifying key: {0}")]
+	InvalidVerifyingKey(anyhow::Error),
+	#[error("digest does not match the payload, the transaction was altered")]
+	DigestMismatch,
+	#[error("digest is not the one expected by the operator, the transaction was replaced")]
+	UnexpectedDigest,
+	#[error("transaction is for {expected} but the signer is {actual}")]
+	WrongSigner { expected: Address, actual: Address },
+	#[error("transaction is already signed")]
+	AlreadySigned,
+	#[error("transaction is not signed")]
+	NotSigned,
+	#[error("invalid signature: {0}")]
+	InvalidSignature(#[from] VerifyError),
+	#[error("signing failed: {0}")]
+	Signing(anyhow::Error),
+}
+
+/// A V2 transaction payload in transit between the online and offline machines, unsigned until
+/// [`OfflineTransaction::sign`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OfflineTransaction {
+	pub version: u32,
+	pub payload: TXSignPayloadV2,
+	/// Compressed public key of the account expected to sign.
+	#[serde(with = "serde_bytes")]
+	pub verifying_key: VerifyingKeyBytes,
+	/// [`TXSignPayloadV2::digest`] of `payload` when the transaction was created.
+	pub digest: [u8; 32],
+	/// Free text for the operators, not covered by the digest or the signature.
+	#[serde(default)]
+	pub memo: String,
+	/// Seconds since the Unix epoch when the transaction was created.
+	pub created_at: u64,
+	#[serde(default, with = "serde_bytes")]
+	pub signature: Option<SignatureBytes>,
+}
+
+/// The binary format: [`MAGIC`] followed by this message.
+#[derive(Clone, PartialEq, Message)]
+struct Envelope {
+	#[prost(uint32, tag = "1")]
+	version: u32,
+	/// The payload, verifying key and signature, which is empty while unsigned.
+	#[prost(message, optional, tag = "2")]
+	request: Option<SubmitTransactionRequestV2>,
+	#[prost(bytes = "vec", tag = "3")]
+	digest: Vec<u8>,
+	#[prost(string, tag = "4")]
+	memo: String,
+	#[prost(uint64, tag = "5")]
+	created_at: u64,
+}
+
+impl OfflineTransaction {
+	/// An unsigned transaction for the account of `verifying_key`.
+	pub fn new(
+		transaction_type: TransactionTypeV2,
+		nonce: Nonce,
+		fee_limit: Balance,
+		verifying_key: VerifyingKeyBytes,
+	) -> Result<Self, OfflineError> {
+		let payload = TXSignPayloadV2 {
+			nonce: nonce.to_string(),
+			transaction_type: transaction_type.into(),
+			fee_limit: fee_limit.to_string(),
+		};
+		let transaction = Self {
+			version: OFFLINE_FORMAT_VERSION,
+			digest: payload.digest(),
+			payload,
+			verifying_key,
+			memo: String::new(),
+			created_at: SystemTime::now()
+				.duration_since(UNIX_EPOCH)
+				.map(|d| d.as_secs())
+				.unwrap_or_default(),
+			signature: None,
+		};
+		transaction.validate()?;
+		Ok(transaction)
+	}
+
+	pub fn with_memo(mut self, memo: impl Into<String>) -> Self {
+		self.memo = memo.into();
+		self
+	}
+
+	/// The account expected to sign.
+	pub fn sender(&self) -> Result<Address, OfflineError> {
+		crate::address_from_verifying_key(&self.verifying_key)
+			.map_err(OfflineError::InvalidVerifyingKey)
+	}
+
+	/// The transaction being signed.
+	pub fn transaction_type(&self) -> Result<TransactionTypeV2, OfflineError> {
+		Ok(self.payload.transaction_type.clone().try_into()?)
+	}
+
+	pub fn nonce(&self) -> Result<Nonce, OfflineError> {
+		number(&self.payload.nonce, "nonce")
+	}
+
+	pub fn fee_limit(&self) -> Result<Balance, OfflineError> {
+		number(&self.payload.fee_limit, "fee_limit")
+	}
+
+	pub fn is_signed(&self) -> bool {
+		self.signature.is_some()
+	}
+
+	/// Checks the version, that the payload is a valid transaction matching `digest` and, when
+	/// signed, the signature.
+	///
+	/// The payload must be the one rebuilt from its parsed fields, as [`crate::verify_v2`] does,
+	/// so a nonce such as `"007"` or `"+7"` is refused with [`OfflineError::NonCanonical`].
+	pub fn validate(&self) -> Result<(), OfflineError> {
+		if self.version != OFFLINE_FORMAT_VERSION {
+			return Err(OfflineError::UnsupportedVersion(self.version))
+		}
+		let canonical = TXSignPayloadV2 {
+			nonce: self.nonce()?.to_string(),
+			transaction_type: self.transaction_type()?.into(),
+			fee_limit: self.fee_limit()?.to_string(),
+		};
+		if canonical != self.payload {
+			return Err(OfflineError::NonCanonical)
+		}
+		self.sender()?;
+		if self.payload.digest() != self.digest {
+			return Err(OfflineError::DigestMismatch)
+		}
+		if let Some(signature) = &self.signature {
+			crate::verify_message(&self.digest, signature, &self.verifying_key)?;
+		}
+		Ok(())
+	}
+
+	/// Signs the unsigned transaction with `signer`, which must hold the key of the account the
+	/// transaction is for. `expected_digest` is the digest the operator copied from the online
+	/// machine; a transaction with any other digest is refused.
+	pub async fn sign(
+		mut self,
+		signer: &dyn Signer,
+		expected_digest: &[u8; 32],
+	) -> Result<Self, OfflineError> {
+		self.validate()?;
+		if self.digest != *expected_digest {
+			return Err(OfflineError::UnexpectedDigest)
+		}
+		if self.is_signed() {
+			return Err(OfflineError::AlreadySigned)
+		}
+		let signature = signer.sign_digest(&self.digest).await.map_err(OfflineError::Signing)?;
+		if signature.verifying_key != self.verifying_key {
+			let actual = crate::address_from_verifying_key(&signature.verifying_key)
+				.map_err(OfflineError::InvalidVerifyingKey)?;
+			return Err(OfflineError::WrongSigner { expected: self.sender()?, actual })
+		}
+		self.signature = Some(signature.signature);
+		Ok(self)
+	}
+
+	/// The request to broadcast, once signed.
+	pub fn into_request(self) -> Result<SubmitTransactionRequestV2, OfflineError> {
+		self.validate()?;
+		if !self.is_signed() {
+			return Err(OfflineError::NotSigned)
+		}
+		let request = self.request()?;
+		crate::verify_v2(&request)?;
+		Ok(request)
+	}
+
+	fn request(&self) -> Result<SubmitTransactionRequestV2, OfflineError> {
+		let transaction_type: submit_transaction_request_v2::TransactionType =
+			self.transaction_type()?.try_into()?;
+		Ok(SubmitTransactionRequestV2 {
+			nonce: self.payload.nonce.clone(),
+			transaction_type: Some(transaction_type),
+			fee_limit: self.payload.fee_limit.clone(),
+			signature: self.signature.clone().unwrap_or_default(),
+			verifying_key: self.verifying_key.clone(),
+		})
+	}
+
+	pub fn to_json(&self) -> Result<String, OfflineError> {
+		Ok(serde_json::to_string_pretty(self)?)
+	}
+
+	/// Parses and [validates](Self::validate) the JSON format.
+	pub fn from_json(json: &str) -> Result<Self, OfflineError> {
+		let transaction: Self = serde_json::from_str(json)?;
+		transaction.validate()?;
+		Ok(transaction)
+	}
+
+	pub fn to_bytes(&self) -> Result<Vec<u8>, OfflineError> {
+		let envelope = Envelope {
+			version: self.version,
+			request: Some(self.request()?),
+			digest: self.digest.to_vec(),
+			memo: self.memo.clone(),
+			created_at: self.created_at,
+		};
+		let mut bytes = MAGIC.to_vec();
+		bytes.extend(envelope.encode_to_vec());
+		Ok(bytes)
+	}
+
+	/// Parses and [validates](Self::validate) the binary format.
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self, OfflineError> {
+		let envelope = Envelope::decode(bytes.strip_prefix(MAGIC).ok_or(OfflineError::BadMagic)?)?;
+		if envelope.version != OFFLINE_FORMAT_VERSION {
+			return Err(OfflineError::UnsupportedVersion(envelope.version))
+		}
+		let request = envelope.request.ok_or(ConversionError::MissingField("request"))?;
+		let transaction_type: TransactionTypeV2 = request
+			.transaction_type
+			.ok_or(ConversionError::MissingField("transaction_type"))?
+			.try_into()
+			.map_err(OfflineError::Malformed)?;
+		let transaction = Self {
+			version: envelope.version,
+			payload: TXSignPayloadV2 {
+				nonce: request.nonce,
+				transaction_type: transaction_type.into(),
+				fee_limit: request.fee_limit,
+			},
+			verifying_key: request.verifying_key,
+			digest: envelope.digest.try_into().map_err(|digest: Vec<u8>| {
+				ConversionError::InvalidHashLength { field: "digest", len: digest.len() }
+			})?,
+			memo: envelope.memo,
+			created_at: envelope.created_at,
+			signature: Some(request.signature).filter(|signature| !signature.is_empty()),
+		};
+		transaction.validate()?;
+		Ok(transaction)
+	}
+}
+
+fn number<T: std::str::FromStr>(value: &str, field: &'static str) -> Result<T, OfflineError> {
+	value
+		.parse()
+		.map_err(|_| OfflineError::InvalidNumber { field, value: value.to_owned() })
+}
@@ -0,0 +1,168 @@
+use crate::{
+	crypto::{PublicKey, SecretKey},
+	primitives::*,
+};
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Compact ECDSA signature over a payload digest, together with the key that verifies it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestSignature {
+	pub signature: SignatureBytes,
+	pub verifying_key: VerifyingKeyBytes,
+}
+
+/// Produces secp256k1 signatures over SHA-256 sign payload digests.
+#[async_trait]
+pub trait Signer: Send + Sync {
+	/// Compressed SEC1 encoding of the signing account's public key.
+	async fn verifying_key(&self) -> Result<VerifyingKeyBytes>;
+
+	/// Signs `digest` and returns the 64-byte compact signature and the verifying key.
+	async fn sign_digest(&self, digest: &[u8; 32]) -> Result<DigestSignature>;
+}
+
+/// Signer backed by a secret key held in process memory.
+pub struct LocalSigner {
+	secret_key: SecretKey,
+}
+
+impl LocalSigner {
+	pub fn new(secret_key: SecretKey) -> Self {
+		Self { secret_key }
+	}
+
+	pub fn from_hex(private_key: &str) -> Result<Self> {
+		Ok(Self::new(SecretKey::from_hex(private_key)?))
+	}
+}
+
+#[async_trait]
+impl Signer for LocalSigner {
+	async fn verifying_key(&self) -> Result<VerifyingKeyBytes> {
+		Ok(self.secret_key.public_key().serialize().to_vec())
+	}
+
+	async fn sign_digest(&self, digest: &[u8; 32]) -> Result<DigestSignature> {
+		Ok(DigestSignature {
+			signature: self.secret_key.sign_digest(digest).to_vec(),
+			verifying_key: self.secret_key.public_key().serialize().to_vec(),
+		})
+	}
+}
+
+#[derive(Debug, Serialize)]
+struct RemoteSignRequest<'a> {
+	key_id: &'a str,
+	digest: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteSignResponse {
+	signature: String,
+	verifying_key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteVerifyingKeyResponse {
+	verifying_key: String,
+}
+
+/// Signer that delegates to a signing service over HTTP.
+///
+/// The service is expected to expose two JSON endpoints, with all bytes hex encoded:
+/// * `GET {url}/keys/{key_id}` returning `{"verifying_key": ..}`
+/// * `POST {url}/sign` taking `{"key_id": .., "digest": ..}` and returning `{"signature": ..,
+///   "verifying_key": ..}`
+///
+/// The key published for `key_id` is fetched once and cached. Every signature returned by the
+/// service must come with that key and is verified against the digest before it is used.
+pub struct RemoteSigner {
+	client: reqwest::Client,
+	url: String,
+	key_id: String,
+	verifying_key: Mutex<Option<VerifyingKeyBytes>>,
+}
+
+impl RemoteSigner {
+	pub fn new(client: reqwest::Client, url: impl Into<String>, key_id: impl Into<String>) -> Self {
+		let url = url.into().trim_end_matches('/').to_owned();
+		Self { client, url, key_id: key_id.into(), verifying_key: Mutex::new(None) }
+	}
+
+	fn cached_verifying_key(&self) -> Option<VerifyingKeyBytes> {
+		self.verifying_key.lock().unwrap_or_else(|e| e.into_inner()).clone()
+	}
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+	async fn verifying_key(&self) -> Result<VerifyingKeyBytes> {
+		if let Some(verifying_key) = self.cached_verifying_key() {
+			return Ok(verifying_key)
+		}
+		let response = self
+			.client
+			.get(format!("{}/keys/{}", self.url, self.key_id))
+			.send()
+			.await?
+			.error_for_status()?
+			.json::<RemoteVerifyingKeyResponse>()
+			.await?;
+		let verifying_key =
+			hex::decode(response.verifying_key).context("failed to decode verifying_key")?;
+		*self.verifying_key.lock().unwrap_or_else(|e| e.into_inner()) = Some(verifying_key.clone());
+		Ok(verifying_key)
+	}
+
+	async fn sign_digest(&self, digest: &[u8; 32]) -> Result<DigestSignature> {
+		let request = RemoteSignRequest { key_id: &self.key_id, digest: hex::encode(digest) };
+		let response = self
+			.client
+			.post(format!("{}/sign", self.url))
+			.json(&request)
+			.send()
+			.await?
+			.error_for_status()?
+			.json::<RemoteSignResponse>()
+			.await?;
+		let signature = DigestSignature {
+			signature: hex::decode(response.signature).context("failed to decode signature")?,
+			verifying_key: hex::decode(response.verifying_key)
+				.context("failed to decode verifying_key")?,
+		};
+
+		if signature.verifying_key != self.verifying_key().await? {
+			return Err(anyhow!(
+				"remote signer signed with {}, not the key published for {}",
+				hex::encode(&signature.verifying_key),
+				self.key_id
+			))
+		}
+		crate::verify_message(digest, &signature.signature, &signature.verifying_key)
+			.map_err(|e| anyhow!("remote signer returned an unusable signature: {}", e))?;
+		Ok(signature)
+	}
+}
+
+/// Signs `digest` with `signer` and appends the recovery id, giving a 65-byte `r || s || v`
+/// signature with `v` either 0 or 1.
+pub async fn sign_digest_recoverable(signer: &dyn Signer, digest: &[u8; 32]) -> Result<[u8; 65]> {
+	let DigestSignature { signature, verifying_key } = signer.sign_digest(digest).await?;
+	if signature.len() != 64 {
+		return Err(anyhow!("Invalid compact signature length {}", signature.len()))
+	}
+	let mut recoverable = [0u8; 65];
+	recoverable[..64].copy_from_slice(&signature);
+	for v in 0..=1 {
+		recoverable[64] = v;
+		if PublicKey::recover_from_digest(digest, &recoverable)
+			.is_ok_and(|key| key.serialize()[..] == verifying_key[..])
+		{
+			return Ok(recoverable)
+		}
+	}
+	Err(anyhow!("signature does not recover to the signer's verifying key"))
+}
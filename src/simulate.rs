@@ -0,0 +1,168 @@
+//! Dry runs of contract calls with `SmartContractReadOnlyCall` before they are sent.
+//!
+//! The read-only call has no sender and no deposit, so a contract that checks either may behave
+//! differently when the transaction is executed for real.
+
+use crate::{
+	fee::FeePolicy,
+	json,
+	primitives::*,
+	rpc_model::{
+		estimate_fee_request, submit_transaction_request_v2, SmartContractFunctionCall,
+		SmartContractReadOnlyCallRequest, SmartContractReadOnlyCallResponse,
+		SmartContractReadOnlyCallStatus, SubmitTransactionRequestV2, SubmitTransactionResponse,
+	},
+	transaction::TransactionTypeV2,
+};
+use anyhow::{anyhow, Context, Result};
+use reqwest::RequestBuilder;
+use serde_json::json;
+#[cfg(feature = "grpc")]
+use {crate::rpc_model::node_client::NodeClient, tonic::transport::Channel};
+
+/// The outcome of a read-only run of a contract call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simulation {
+	pub status: SmartContractReadOnlyCallStatus,
+	/// The bytes the call returned.
+	pub result: Vec<u8>,
+	/// `result` parsed as JSON, if it is JSON.
+	pub decoded: Option<serde_json::Value>,
+	/// The node's fee estimate for sending the call as a transaction.
+	pub fee_estimate: Balance,
+}
+
+impl Simulation {
+	pub fn succeeded(&self) -> bool {
+		self.status == SmartContractReadOnlyCallStatus::Succeeded
+	}
+
+	fn new(response: SmartContractReadOnlyCallResponse, fee_estimate: Balance) -> Result<Self> {
+		let status = SmartContractReadOnlyCallStatus::from_i32(response.status)
+			.ok_or(anyhow!("Invalid read-only call status {}", response.status))?;
+		Ok(Self {
+			status,
+			decoded: serde_json::from_slice(&response.result).ok(),
+			result: response.result,
+			fee_estimate,
+		})
+	}
+}
+
+/// The call was not submitted because its simulation did not succeed.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("Simulation of the contract call did not succeed: {}", .0.status.as_str_name())]
+pub struct SimulationFailed(pub Box<Simulation>);
+
+/// Runs `transaction_type`, which must be a contract function call, as a read-only call over
+/// gRPC and estimates its fee for the account of `verifying_key`.
+#[cfg(feature = "grpc")]
+pub async fn simulate_grpc(
+	client: &mut NodeClient<Channel>,
+	transaction_type: TransactionTypeV2,
+	verifying_key: VerifyingKeyBytes,
+) -> Result<Simulation> {
+	let request = read_only_call_request(&transaction_type)?;
+	let response = client.smart_contract_read_only_call(request).await?.into_inner();
+	let estimate = estimate_fee_request::TransactionType::try_from(transaction_type)?;
+	let fee_estimate = FeePolicy::Estimate.fee_limit_grpc(client, estimate, verifying_key).await?;
+	Simulation::new(response, fee_estimate)
+}
+
+/// Like [`simulate_grpc`], over JSON-RPC. `client` must be cloneable, i.e. not carry a streaming
+/// body.
+pub async fn simulate_json_rpc(
+	client: RequestBuilder,
+	transaction_type: TransactionTypeV2,
+	verifying_key: VerifyingKeyBytes,
+) -> Result<Simulation> {
+	let request = read_only_call_request(&transaction_type)?;
+	let response = json::post_json_rpc(
+		client.try_clone().context("JSON-RPC request is not cloneable")?,
+		"l1x_smartContractReadOnlyCall",
+		json!({ "request": request }),
+	)
+	.await?;
+	let response = json::parse_response(response)?;
+	let estimate = estimate_fee_request::TransactionType::try_from(transaction_type)?;
+	let fee_estimate =
+		FeePolicy::Estimate.fee_limit_json_rpc(client, estimate, verifying_key).await?;
+	Simulation::new(response, fee_estimate)
+}
+
+/// Submits `request` over gRPC, first simulating it when it is a contract function call and
+/// refusing with [`SimulationFailed`] when the simulation does not succeed. Other transactions
+/// are submitted as they are.
+#[cfg(feature = "grpc")]
+pub async fn submit_simulated(
+	client: &mut NodeClient<Channel>,
+	request: SubmitTransactionRequestV2,
+) -> Result<SubmitTransactionResponse> {
+	if let Some(call) = simulated_call(&request)? {
+		let simulation = simulate_grpc(client, call, request.verifying_key.clone()).await?;
+		if !simulation.succeeded() {
+			return Err(SimulationFailed(Box::new(simulation)).into())
+		}
+	}
+	client
+		.submit_transaction_v2(request)
+		.await?
+		.into_inner()
+		.message()
+		.await?
+		.ok_or(anyhow!("No response to the submitted transaction"))
+}
+
+/// Like [`submit_simulated`], over JSON-RPC. `client` must be cloneable, i.e. not carry a
+/// streaming body.
+pub async fn submit_simulated_json_rpc(
+	client: RequestBuilder,
+	request: SubmitTransactionRequestV2,
+) -> Result<SubmitTransactionResponse> {
+	if let Some(call) = simulated_call(&request)? {
+		let simulation = simulate_json_rpc(
+			client.try_clone().context("JSON-RPC request is not cloneable")?,
+			call,
+			request.verifying_key.clone(),
+		)
+		.await?;
+		if !simulation.succeeded() {
+			return Err(SimulationFailed(Box::new(simulation)).into())
+		}
+	}
+	let response =
+		json::post_json_rpc(client, "l1x_submitTransactionV2", json!({ "request": request }))
+			.await?;
+	json::parse_response(response)
+}
+
+/// The transaction of `request` if it is a contract function call.
+fn simulated_call(request: &SubmitTransactionRequestV2) -> Result<Option<TransactionTypeV2>> {
+	match &request.transaction_type {
+		Some(
+			call @ submit_transaction_request_v2::TransactionType::SmartContractFunctionCall(_),
+		) => Ok(Some(call.clone().try_into()?)),
+		_ => Ok(None),
+	}
+}
+
+fn read_only_call_request(
+	transaction_type: &TransactionTypeV2,
+) -> Result<SmartContractReadOnlyCallRequest> {
+	let TransactionTypeV2::SmartContractFunctionCall {
+		contract_instance_address,
+		function,
+		arguments,
+		..
+	} = transaction_type
+	else {
+		return Err(anyhow!("Only smart contract function calls can be simulated"))
+	};
+	Ok(SmartContractReadOnlyCallRequest {
+		call: Some(SmartContractFunctionCall {
+			contract_address: contract_instance_address.to_vec(),
+			function_name: function.clone(),
+			arguments: arguments.clone(),
+		}),
+	})
+}
@@ -0,0 +1,224 @@
+//! Following a submitted transaction until it is included and confirmed.
+//!
+//! Polling sleeps on the tokio timer, so the module needs the `tracker` feature, which is on by
+//! default.
+//!
+//! ```ignore
+//! let submitted = client.submit_transaction_v2(request).await?.into_inner().message().await?;
+//! let tracker = TransactionTracker::new(client.clone(), TrackerConfig::default());
+//! match tracker.track(submitted.ok_or(anyhow!("no response"))?).await? {
+//!     Outcome::Confirmed(receipt) => println!("fee used: {}", receipt.fee_used),
+//!     Outcome::Failed(receipt) => println!("failed in block {}", receipt.block_number),
+//!     Outcome::Unconfirmed { receipt, confirmations, required } =>
+//!         println!("{} has {} of {} confirmations", receipt.hash, confirmations, required),
+//!     Outcome::Dropped { hash } => println!("{} was dropped", hash),
+//! }
+//! ```
+
+use crate::{
+	primitives::*,
+	rpc_model::{GetTransactionV3ReceiptResponse, SubmitTransactionResponse, TransactionStatus},
+	transaction::TransactionV3Response,
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+#[cfg(feature = "grpc")]
+use {
+	crate::rpc_model::{
+		node_client::NodeClient, GetChainStateRequest, GetTransactionReceiptRequest,
+	},
+	anyhow::Context,
+	tonic::{transport::Channel, Code},
+};
+
+/// Where a [`TransactionTracker`] reads receipts and the chain head from.
+#[async_trait]
+pub trait ChainSource: Send + Sync {
+	/// The receipt of the transaction with `hash`, `None` while it is not included.
+	async fn receipt(&self, hash: &str) -> Result<Option<GetTransactionV3ReceiptResponse>>;
+
+	/// The number of the chain's head block.
+	async fn head_block_number(&self) -> Result<BlockNumber>;
+}
+
+/// Reads receipts with `GetTransactionV3Receipt` and the head with `GetChainState`. A
+/// `NOT_FOUND` status or a receipt without a transaction means the transaction is not included
+/// yet.
+#[cfg(feature = "grpc")]
+#[async_trait]
+impl ChainSource for NodeClient<Channel> {
+	async fn receipt(&self, hash: &str) -> Result<Option<GetTransactionV3ReceiptResponse>> {
+		let request = GetTransactionReceiptRequest { hash: hash.to_owned() };
+		match self.clone().get_transaction_v3_receipt(request).await {
+			Ok(response) => {
+				let response = response.into_inner();
+				Ok(response.transaction.is_some().then_some(response))
+			},
+			Err(status) if status.code() == Code::NotFound => Ok(None),
+			Err(status) => Err(status.into()),
+		}
+	}
+
+	async fn head_block_number(&self) -> Result<BlockNumber> {
+		let response = self.clone().get_chain_state(GetChainStateRequest {}).await?.into_inner();
+		response.head_block_number.parse().context("failed to parse head block number")
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackerConfig {
+	/// Blocks built on top of the including block before the transaction is confirmed.
+	pub confirmations: u64,
+	/// First delay between polls, doubled after every poll up to `max_backoff`.
+	pub initial_backoff: Duration,
+	pub max_backoff: Duration,
+	/// How long the transaction may take to be included before it is reported as dropped.
+	pub inclusion_timeout: Duration,
+	/// How long confirmations may take after inclusion.
+	pub confirmation_timeout: Duration,
+}
+
+impl Default for TrackerConfig {
+	fn default() -> Self {
+		Self {
+			confirmations: 1,
+			initial_backoff: Duration::from_millis(500),
+			max_backoff: Duration::from_secs(8),
+			inclusion_timeout: Duration::from_secs(120),
+			confirmation_timeout: Duration::from_secs(300),
+		}
+	}
+}
+
+/// An included transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Receipt {
+	pub hash: String,
+	pub status: TransactionStatus,
+	pub block_number: BlockNumber,
+	pub fee_used: Balance,
+	/// The contract deployed or instantiated by the transaction, from the submit response.
+	pub contract_address: Option<String>,
+	pub transaction: TransactionV3Response,
+}
+
+/// What happened to a submitted transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+	/// Executed successfully and buried under the configured number of blocks.
+	Confirmed(Receipt),
+	/// Included with a failed status, reported as soon as it is included.
+	Failed(Receipt),
+	/// Included, but with fewer than `required` confirmations when the confirmation timeout
+	/// passed.
+	Unconfirmed { receipt: Receipt, confirmations: u64, required: u64 },
+	/// Not included within the inclusion timeout.
+	Dropped { hash: String },
+}
+
+/// Polls a node with backoff until a submitted transaction is included and confirmed.
+pub struct TransactionTracker {
+	source: Box<dyn ChainSource>,
+	config: TrackerConfig,
+}
+
+impl TransactionTracker {
+	pub fn new(source: impl ChainSource + 'static, config: TrackerConfig) -> Self {
+		Self { source: Box::new(source), config }
+	}
+
+	/// Follows the transaction of a `SubmitTransactionV2` response to its outcome. Fails only
+	/// when the node cannot be reached or its responses cannot be decoded.
+	pub async fn track(&self, submitted: SubmitTransactionResponse) -> Result<Outcome> {
+		let Some(receipt) = self.await_inclusion(&submitted).await? else {
+			return Ok(Outcome::Dropped { hash: submitted.hash })
+		};
+		if receipt.status == TransactionStatus::Failed {
+			return Ok(Outcome::Failed(receipt))
+		}
+		self.await_confirmations(receipt).await
+	}
+
+	/// The receipt of the submitted transaction, `None` if it is not included in time.
+	pub async fn await_inclusion(
+		&self,
+		submitted: &SubmitTransactionResponse,
+	) -> Result<Option<Receipt>> {
+		let mut poll = Backoff::new(&self.config, self.config.inclusion_timeout);
+		loop {
+			if let Some(response) = self.source.receipt(&submitted.hash).await? {
+				return receipt(submitted, response).map(Some)
+			}
+			if !poll.wait().await {
+				return Ok(None)
+			}
+		}
+	}
+
+	/// Waits until `receipt`'s block has the configured number of blocks on top of it, giving
+	/// [`Outcome::Confirmed`], or [`Outcome::Unconfirmed`] once the confirmation timeout passes.
+	pub async fn await_confirmations(&self, receipt: Receipt) -> Result<Outcome> {
+		let required = self.config.confirmations;
+		let mut poll = Backoff::new(&self.config, self.config.confirmation_timeout);
+		loop {
+			let head = self.source.head_block_number().await?;
+			let confirmations =
+				u64::try_from(head.saturating_sub(receipt.block_number)).unwrap_or(u64::MAX);
+			if confirmations >= required {
+				return Ok(Outcome::Confirmed(receipt))
+			}
+			if !poll.wait().await {
+				return Ok(Outcome::Unconfirmed { receipt, confirmations, required })
+			}
+		}
+	}
+}
+
+fn receipt(
+	submitted: &SubmitTransactionResponse,
+	response: GetTransactionV3ReceiptResponse,
+) -> Result<Receipt> {
+	let status = TransactionStatus::from_i32(response.status)
+		.ok_or(anyhow!("Invalid transaction status {}", response.status))?;
+	let transaction = TransactionV3Response::try_from(
+		response.transaction.ok_or(anyhow!("Receipt without a transaction"))?,
+	)?;
+	Ok(Receipt {
+		hash: submitted.hash.clone(),
+		status,
+		block_number: transaction.block_number,
+		fee_used: transaction.fee_used,
+		contract_address: submitted.contract_address.clone(),
+		transaction,
+	})
+}
+
+/// Exponentially growing delays between polls, up to a deadline.
+struct Backoff {
+	delay: Duration,
+	max: Duration,
+	deadline: Instant,
+}
+
+impl Backoff {
+	fn new(config: &TrackerConfig, timeout: Duration) -> Self {
+		Self {
+			delay: config.initial_backoff,
+			max: config.max_backoff,
+			deadline: Instant::now() + timeout,
+		}
+	}
+
+	/// Sleeps until the next poll, or returns `false` once the deadline has passed.
+	async fn wait(&mut self) -> bool {
+		let remaining = self.deadline.saturating_duration_since(Instant::now());
+		if remaining.is_zero() {
+			return false
+		}
+		sleep(self.delay.min(remaining)).await;
+		self.delay = (self.delay * 2).min(self.max);
+		true
+	}
+}
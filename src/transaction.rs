@@ -90,6 +90,41 @@ pub struct TransactionV3 {
 	pub eth_original_transaction: Option<Vec<u8>>,
 }
 
+/// A V3 transaction as included in a block, with its sender and inclusion details.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionV3Response {
+	pub transaction: TransactionV3,
+	pub from: Address,
+	pub transaction_hash: TransactionHash,
+	pub block_hash: BlockHash,
+	pub block_number: BlockNumber,
+	pub fee_used: Balance,
+	pub timestamp: TimeStamp,
+}
+
+impl TransactionV3 {
+	/// Address of the account that signed the transaction, established from the signature alone.
+	///
+	/// Ethereum-originated transactions are attributed to the signer of the original Ethereum
+	/// transaction. Native transactions with a 65-byte recoverable signature are attributed to the
+	/// recovered key, and otherwise the signature is checked against `verifying_key`.
+	pub fn recover_sender(&self) -> Result<crate::address::Address, Error> {
+		if let Some(eth_tx) = &self.eth_original_transaction {
+			return crate::eth_transaction::recover_sender(eth_tx)
+		}
+		let payload = crate::TXSignPayloadV2 {
+			nonce: self.nonce.to_string(),
+			transaction_type: self.transaction_type.clone().into(),
+			fee_limit: self.fee_limit.to_string(),
+		};
+		if self.signature.len() == 65 {
+			return crate::recover_address(&payload, &self.signature)
+		}
+		crate::verify_message(&payload.digest(), &self.signature, &self.verifying_key)?;
+		crate::address_from_verifying_key(&self.verifying_key)
+	}
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TransactionType {
 	NativeTokenTransfer(Address, Balance),
@@ -0,0 +1,86 @@
+//! Migration of V1 `SubmitTransactionRequest`s to re-signed `SubmitTransactionRequestV2`s.
+
+use crate::{
+	primitives::*,
+	rpc_model::{
+		submit_transaction_request, submit_transaction_request_v2, SubmitTransactionRequest,
+		SubmitTransactionRequestV2,
+	},
+	signer::Signer,
+	transaction::TransactionType,
+};
+use anyhow::{anyhow, Context, Result};
+use std::fmt;
+
+/// A difference in meaning between a V1 request and its V2 upgrade.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpgradeChange {
+	/// The `value` of a V1 deployment is sent as the `deposit` of the V2 deployment.
+	ValueAsDeposit { value: u64 },
+}
+
+impl fmt::Display for UpgradeChange {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			UpgradeChange::ValueAsDeposit { value } =>
+				write!(f, "deployment value {} is sent as the deposit", value),
+		}
+	}
+}
+
+/// A V2 request signed over the V2 payload, with the changes the upgrade made.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpgradedRequest {
+	pub request: SubmitTransactionRequestV2,
+	pub changes: Vec<UpgradeChange>,
+}
+
+/// Converts `request` to V2 and signs it with `signer` using [`crate::sign_v2`].
+///
+/// The V1 request must [verify](crate::verify), failing with the [`crate::VerifyError`]
+/// otherwise, and be from the same account as `signer`, so the upgrade cannot change the sender
+/// or the transaction the sender signed.
+pub async fn upgrade_request(
+	request: SubmitTransactionRequest,
+	signer: &dyn Signer,
+) -> Result<UpgradedRequest> {
+	crate::verify(&request).context("V1 request does not verify")?;
+	let transaction_type = request.transaction_type.ok_or(anyhow!("Missing transaction_type"))?;
+	TransactionType::try_from(transaction_type.clone())?;
+	let nonce: Nonce = request.nonce.parse().map_err(|_| anyhow!("Failed to parse nonce"))?;
+	let fee_limit: Balance =
+		request.fee_limit.parse().map_err(|_| anyhow!("Failed to parse fee_limit"))?;
+
+	let verifying_key = signer.verifying_key().await?;
+	let sender = crate::address_from_verifying_key(&request.verifying_key)?;
+	let signer_address = crate::address_from_verifying_key(&verifying_key)?;
+	if sender != signer_address {
+		return Err(anyhow!(
+			"V1 request is from {} but the signer is {}",
+			sender.to_checksum(),
+			signer_address.to_checksum()
+		))
+	}
+
+	let mut changes = Vec::new();
+	if let submit_transaction_request::TransactionType::SmartContractDeployment(deployment) =
+		&transaction_type
+	{
+		if deployment.value != 0 {
+			changes.push(UpgradeChange::ValueAsDeposit { value: deployment.value });
+		}
+	}
+
+	let transaction_type = submit_transaction_request_v2::TransactionType::from(transaction_type);
+	let signature = crate::sign_v2(signer, transaction_type.clone(), fee_limit, nonce).await?;
+	Ok(UpgradedRequest {
+		request: SubmitTransactionRequestV2 {
+			nonce: nonce.to_string(),
+			transaction_type: Some(transaction_type),
+			fee_limit: fee_limit.to_string(),
+			signature,
+			verifying_key,
+		},
+		changes,
+	})
+}
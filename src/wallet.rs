@@ -0,0 +1,231 @@
+//! BIP-39 mnemonics and BIP-32/BIP-44 hierarchical deterministic key derivation.
+
+use crate::crypto::SecretKey;
+use anyhow::{anyhow, Result};
+use bip39::Mnemonic;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha512;
+use std::{fmt, ops::Range, str::FromStr};
+
+/// BIP-44 account path shared with Ethereum wallets, so a mnemonic yields the same addresses in
+/// L1X tooling and in Ethereum wallets such as MetaMask.
+pub const DEFAULT_ACCOUNT_PATH: &str = "m/44'/60'/0'/0";
+
+const HARDENED_BIT: u32 = 1 << 31;
+
+/// Generates a new English mnemonic of 12, 15, 18, 21 or 24 words.
+pub fn generate_mnemonic(word_count: usize) -> Result<Mnemonic> {
+	if !(12..=24).contains(&word_count) || !word_count.is_multiple_of(3) {
+		return Err(anyhow!("Invalid mnemonic word count {}", word_count))
+	}
+	let mut entropy = vec![0u8; word_count / 3 * 4];
+	rand::thread_rng().fill_bytes(&mut entropy);
+	Ok(Mnemonic::from_entropy(&entropy)?)
+}
+
+/// Parses `phrase` and checks its word list and checksum.
+pub fn validate_mnemonic(phrase: &str) -> Result<Mnemonic> {
+	Mnemonic::parse(phrase).map_err(|e| anyhow!("Invalid mnemonic: {}", e))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChildNumber {
+	Normal(u32),
+	Hardened(u32),
+}
+
+impl ChildNumber {
+	/// A non-hardened child. `index` must be below 2^31.
+	pub fn normal(index: u32) -> Result<Self> {
+		Ok(ChildNumber::Normal(check_index(index)?))
+	}
+
+	/// A hardened child. `index` must be below 2^31, the hardened bit is added on derivation.
+	pub fn hardened(index: u32) -> Result<Self> {
+		Ok(ChildNumber::Hardened(check_index(index)?))
+	}
+
+	fn index(&self) -> u32 {
+		match self {
+			ChildNumber::Normal(i) => *i,
+			ChildNumber::Hardened(i) => i | HARDENED_BIT,
+		}
+	}
+}
+
+fn check_index(index: u32) -> Result<u32> {
+	if index & HARDENED_BIT != 0 {
+		return Err(anyhow!("Child index {} is out of range", index))
+	}
+	Ok(index)
+}
+
+impl fmt::Display for ChildNumber {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ChildNumber::Normal(i) => write!(f, "{}", i),
+			ChildNumber::Hardened(i) => write!(f, "{}'", i),
+		}
+	}
+}
+
+/// A BIP-32 derivation path such as `m/44'/60'/0'/0/0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivationPath(Vec<ChildNumber>);
+
+impl DerivationPath {
+	pub fn child(&self, child: ChildNumber) -> Self {
+		let mut path = self.0.clone();
+		path.push(child);
+		Self(path)
+	}
+
+	pub fn children(&self) -> &[ChildNumber] {
+		&self.0
+	}
+}
+
+impl FromStr for DerivationPath {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self> {
+		let mut parts = s.split('/');
+		if parts.next() != Some("m") {
+			return Err(anyhow!("Derivation path must start with m: {}", s))
+		}
+		parts
+			.map(|part| {
+				let (index, hardened) = match part.strip_suffix(['\'', 'h', 'H']) {
+					Some(index) => (index, true),
+					None => (part, false),
+				};
+				let index: u32 =
+					index.parse().map_err(|_| anyhow!("Invalid path component {}", part))?;
+				if hardened {
+					ChildNumber::hardened(index)
+				} else {
+					ChildNumber::normal(index)
+				}
+				.map_err(|_| anyhow!("Path component {} is out of range", part))
+			})
+			.collect::<Result<_>>()
+			.map(Self)
+	}
+}
+
+impl fmt::Display for DerivationPath {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "m")?;
+		for child in &self.0 {
+			write!(f, "/{}", child)?;
+		}
+		Ok(())
+	}
+}
+
+/// A BIP-32 extended private key.
+#[derive(Clone)]
+pub struct ExtendedPrivateKey {
+	pub secret_key: SecretKey,
+	pub chain_code: [u8; 32],
+	pub depth: u8,
+}
+
+impl ExtendedPrivateKey {
+	pub fn new_master(seed: &[u8]) -> Result<Self> {
+		let mut mac = Hmac::<Sha512>::new_from_slice(b"Bitcoin seed")?;
+		mac.update(seed);
+		let output = mac.finalize().into_bytes();
+		Ok(Self {
+			secret_key: SecretKey::from_slice(&output[..32])?,
+			chain_code: output[32..].try_into()?,
+			depth: 0,
+		})
+	}
+
+	pub fn derive_child(&self, child: ChildNumber) -> Result<Self> {
+		let (ChildNumber::Normal(index) | ChildNumber::Hardened(index)) = child;
+		check_index(index)?;
+		let mut mac = Hmac::<Sha512>::new_from_slice(&self.chain_code)?;
+		match child {
+			ChildNumber::Hardened(_) => {
+				mac.update(&[0]);
+				mac.update(&self.secret_key.secret_bytes()[..]);
+			},
+			ChildNumber::Normal(_) => {
+				mac.update(&self.secret_key.public_key().serialize());
+			},
+		}
+		mac.update(&child.index().to_be_bytes());
+		let output = mac.finalize().into_bytes();
+
+		let secret_key = self
+			.secret_key
+			.add_tweak(output[..32].try_into()?)
+			.map_err(|_| anyhow!("Derived an invalid key for child {}", child))?;
+		Ok(Self {
+			secret_key,
+			chain_code: output[32..].try_into()?,
+			depth: self.depth.checked_add(1).ok_or(anyhow!("Maximum derivation depth reached"))?,
+		})
+	}
+
+	pub fn derive_path(&self, path: &DerivationPath) -> Result<Self> {
+		path.children().iter().try_fold(self.clone(), |key, child| key.derive_child(*child))
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivedAddress {
+	pub path: DerivationPath,
+	pub address: String,
+}
+
+/// HD wallet rooted at a single BIP-39 seed.
+pub struct HdWallet {
+	master: ExtendedPrivateKey,
+}
+
+impl HdWallet {
+	pub fn from_seed(seed: &[u8]) -> Result<Self> {
+		Ok(Self { master: ExtendedPrivateKey::new_master(seed)? })
+	}
+
+	pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str) -> Result<Self> {
+		Self::from_seed(&mnemonic.to_seed(passphrase))
+	}
+
+	pub fn from_phrase(phrase: &str, passphrase: &str) -> Result<Self> {
+		Self::from_mnemonic(&validate_mnemonic(phrase)?, passphrase)
+	}
+
+	pub fn derive(&self, path: &DerivationPath) -> Result<SecretKey> {
+		Ok(self.master.derive_path(path)?.secret_key)
+	}
+
+	/// Secret key of account `index` under [`DEFAULT_ACCOUNT_PATH`].
+	pub fn account(&self, index: u32) -> Result<SecretKey> {
+		self.derive(&Self::account_path(index)?)
+	}
+
+	/// L1X addresses of the accounts in `indexes` under [`DEFAULT_ACCOUNT_PATH`].
+	pub fn addresses(&self, indexes: Range<u32>) -> Result<Vec<DerivedAddress>> {
+		let parent_path: DerivationPath = DEFAULT_ACCOUNT_PATH.parse()?;
+		let parent = self.master.derive_path(&parent_path)?;
+		indexes
+			.map(|index| {
+				let child = ChildNumber::normal(index)?;
+				let secret_key = parent.derive_child(child)?.secret_key;
+				Ok(DerivedAddress {
+					path: parent_path.child(child),
+					address: crate::get_address_from_private_key(&secret_key)?,
+				})
+			})
+			.collect()
+	}
+
+	fn account_path(index: u32) -> Result<DerivationPath> {
+		Ok(DEFAULT_ACCOUNT_PATH.parse::<DerivationPath>()?.child(ChildNumber::normal(index)?))
+	}
+}
@@ -0,0 +1,90 @@
+use l1x_rpc::address::{Address, AddressError};
+use serde::{de::value::BytesDeserializer, Deserialize};
+
+/// The test vectors of EIP-55.
+const CHECKSUMMED: [&str; 8] = [
+	// All caps
+	"0x52908400098527886E0F7030069857D2E4169EE7",
+	"0x8617E340B3D01FA5F11F306F4090FD50E238070D",
+	// All lower
+	"0xde709f2102306220921060314715629080e2fb77",
+	"0x27b1fdb04752bbc536007a920d24acb045561c26",
+	// Normal
+	"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+	"0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+	"0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+	"0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+];
+
+#[test]
+fn eip55_checksums() {
+	for checksummed in CHECKSUMMED {
+		let address: Address = checksummed.parse().unwrap();
+		assert_eq!(address.to_checksum(), checksummed);
+		assert_eq!(address.to_string(), checksummed[2..].to_lowercase());
+		// Single-case input carries no checksum.
+		assert_eq!(checksummed.to_lowercase().parse::<Address>(), Ok(address));
+		assert_eq!(checksummed[2..].to_uppercase().parse::<Address>(), Ok(address));
+	}
+}
+
+#[test]
+fn wrong_case_checksum_is_rejected() {
+	for checksummed in &CHECKSUMMED[4..] {
+		// Flip the case of the first letter.
+		let at = checksummed[2..].find(|c: char| c.is_ascii_alphabetic()).unwrap() + 2;
+		let mut wrong = checksummed.to_string();
+		let flipped = wrong.as_bytes()[at] ^ 0x20;
+		wrong.replace_range(at..at + 1, &(flipped as char).to_string());
+		assert_eq!(
+			Address::parse_field(&wrong, "recipient"),
+			Err(AddressError::InvalidChecksum { field: "recipient" })
+		);
+	}
+}
+
+#[test]
+fn field_errors_name_the_field() {
+	let error = Address::from_field(&[0; 19], "contract_address").unwrap_err();
+	assert_eq!(error, AddressError::InvalidLength { field: "contract_address", len: 19 });
+	assert!(error.to_string().contains("`contract_address`"));
+
+	let error = Address::parse_field("0xzz", "recipient").unwrap_err();
+	assert!(matches!(error, AddressError::InvalidHex { field: "recipient", .. }));
+	assert!(error.to_string().contains("`recipient`"));
+
+	assert_eq!(
+		Address::parse_field(&"ab".repeat(21), "to"),
+		Err(AddressError::InvalidLength { field: "to", len: 21 })
+	);
+	assert_eq!(Address::from_field(&[7; 20], "to"), Ok(Address([7; 20])));
+}
+
+#[test]
+fn serde_forms() {
+	let address: Address = CHECKSUMMED[4].parse().unwrap();
+
+	// str
+	let json = serde_json::to_string(&address).unwrap();
+	assert_eq!(json, format!("\"{}\"", address));
+	assert_eq!(serde_json::from_str::<Address>(&json).unwrap(), address);
+	let checksummed = format!("\"{}\"", CHECKSUMMED[4]);
+	assert_eq!(serde_json::from_str::<Address>(&checksummed).unwrap(), address);
+	let wrong_case = checksummed.replace("aAeb", "aaeB");
+	assert!(serde_json::from_str::<Address>(&wrong_case)
+		.unwrap_err()
+		.to_string()
+		.contains("checksum"));
+
+	// bytes
+	let bytes = BytesDeserializer::<serde_json::Error>::new(address.as_bytes());
+	assert_eq!(Address::deserialize(bytes).unwrap(), address);
+	let short = BytesDeserializer::<serde_json::Error>::new(&address.as_bytes()[..19]);
+	assert!(Address::deserialize(short).unwrap_err().to_string().contains("length 19"));
+
+	// seq
+	let seq = serde_json::to_string(&address.as_bytes().to_vec()).unwrap();
+	assert_eq!(serde_json::from_str::<Address>(&seq).unwrap(), address);
+	assert!(serde_json::from_str::<Address>("[1, 2, 3]").is_err());
+	assert!(serde_json::from_str::<Address>("[256]").is_err());
+}
@@ -0,0 +1,207 @@
+//! The active `crypto` backend must be byte-identical to libsecp256k1.
+//!
+//! Under the default features this checks the wrapper, run it with
+//! `cargo test --no-default-features --features k256` to check the pure-Rust backend.
+
+use l1x_rpc::{
+	crypto::{Error, PublicKey, SecretKey},
+	signer::LocalSigner,
+};
+use secp256k1::{ecdsa, Message, Scalar, SECP256K1};
+use sha2::{Digest, Sha256};
+
+const ORDER: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+
+fn sha256(data: impl AsRef<[u8]>) -> [u8; 32] {
+	Sha256::digest(data).into()
+}
+
+fn key_pairs() -> Vec<(SecretKey, secp256k1::SecretKey)> {
+	let mut raw: Vec<[u8; 32]> = (0..64u32).map(|i| sha256(i.to_be_bytes())).collect();
+	let mut one = [0u8; 32];
+	one[31] = 1;
+	let mut order_minus_one: [u8; 32] = hex::decode(ORDER).unwrap().try_into().unwrap();
+	order_minus_one[31] -= 1;
+	raw.extend([one, order_minus_one]);
+	raw.iter()
+		.map(|bytes| {
+			(
+				SecretKey::from_slice(bytes).unwrap(),
+				secp256k1::SecretKey::from_slice(bytes).unwrap(),
+			)
+		})
+		.collect()
+}
+
+fn digests() -> Vec<[u8; 32]> {
+	let mut digests: Vec<[u8; 32]> = (0..8u32).map(|i| sha256(format!("digest {}", i))).collect();
+	digests.extend([[0u8; 32], [0xff; 32]]);
+	digests
+}
+
+fn high_s(signature: &[u8; 64]) -> [u8; 64] {
+	let order = hex::decode(ORDER).unwrap();
+	let mut high = *signature;
+	let mut borrow = 0i16;
+	for i in (0..32).rev() {
+		let diff = order[i] as i16 - signature[32 + i] as i16 - borrow;
+		high[32 + i] = diff.rem_euclid(256) as u8;
+		borrow = (diff < 0) as i16;
+	}
+	high
+}
+
+#[test]
+fn public_keys_match() {
+	for (key, reference) in key_pairs() {
+		let reference = reference.public_key(SECP256K1);
+		assert_eq!(key.public_key().serialize(), reference.serialize());
+		assert_eq!(key.public_key().serialize_uncompressed(), reference.serialize_uncompressed());
+		assert_eq!(
+			PublicKey::from_slice(&reference.serialize_uncompressed()).unwrap(),
+			key.public_key()
+		);
+	}
+}
+
+#[test]
+fn signatures_match() {
+	for (key, reference) in key_pairs() {
+		for digest in digests() {
+			let message = Message::from_digest(digest);
+			let expected = SECP256K1.sign_ecdsa(&message, &reference).serialize_compact();
+			assert_eq!(key.sign_digest(&digest), expected);
+
+			let (recovery_id, compact) =
+				SECP256K1.sign_ecdsa_recoverable(&message, &reference).serialize_compact();
+			let recoverable = key.sign_digest_recoverable(&digest);
+			assert_eq!(recoverable[..64], compact);
+			assert_eq!(recoverable[64] as i32, recovery_id.to_i32());
+		}
+	}
+}
+
+#[test]
+fn verification_matches() {
+	let pairs = key_pairs();
+	for (key, reference) in &pairs {
+		for digest in digests() {
+			let signature = SECP256K1
+				.sign_ecdsa(&Message::from_digest(digest), reference)
+				.serialize_compact();
+			assert_eq!(key.public_key().verify_digest(&digest, &signature), Ok(()));
+
+			let wrong_key = pairs[0].0.public_key();
+			if wrong_key != key.public_key() {
+				assert_eq!(
+					wrong_key.verify_digest(&digest, &signature),
+					Err(Error::IncorrectSignature)
+				);
+			}
+
+			let high = high_s(&signature);
+			assert!(ecdsa::Signature::from_compact(&high)
+				.and_then(|sig| SECP256K1.verify_ecdsa(
+					&Message::from_digest(digest),
+					&sig,
+					&reference.public_key(SECP256K1)
+				))
+				.is_err());
+			assert_eq!(
+				key.public_key().verify_digest(&digest, &high),
+				Err(Error::IncorrectSignature)
+			);
+		}
+	}
+
+	let public_key = pairs[0].0.public_key();
+	let digest = digests()[0];
+	assert_eq!(public_key.verify_digest(&digest, &[0u8; 64]), Err(Error::IncorrectSignature));
+	assert_eq!(public_key.verify_digest(&digest, &[0xff; 64]), Err(Error::InvalidSignature));
+	assert_eq!(public_key.verify_digest(&digest, &[1u8; 63]), Err(Error::InvalidSignature));
+}
+
+#[test]
+fn recovery_matches() {
+	for (key, reference) in key_pairs() {
+		for digest in digests() {
+			let signature = key.sign_digest_recoverable(&digest);
+			let recovered = PublicKey::recover_from_digest(&digest, &signature).unwrap();
+			assert_eq!(recovered.serialize(), reference.public_key(SECP256K1).serialize());
+
+			let mut eth = signature;
+			eth[64] += 27;
+			assert_eq!(PublicKey::recover_from_digest(&digest, &eth).unwrap(), recovered);
+
+			// libsecp256k1 also recovers from the high `s` form with the other recovery id.
+			let mut high = [0u8; 65];
+			high[..64].copy_from_slice(&high_s(signature[..64].try_into().unwrap()));
+			high[64] = signature[64] ^ 1;
+			let reference_recovered = SECP256K1
+				.recover_ecdsa(
+					&Message::from_digest(digest),
+					&ecdsa::RecoverableSignature::from_compact(
+						&high[..64],
+						ecdsa::RecoveryId::from_i32(high[64] as i32).unwrap(),
+					)
+					.unwrap(),
+				)
+				.unwrap();
+			assert_eq!(
+				PublicKey::recover_from_digest(&digest, &high).unwrap().serialize(),
+				reference_recovered.serialize()
+			);
+		}
+	}
+
+	let mut invalid = [0u8; 65];
+	invalid[64] = 2;
+	assert_eq!(PublicKey::recover_from_digest(&[0u8; 32], &invalid), Err(Error::InvalidRecoveryId));
+}
+
+#[test]
+fn invalid_secret_keys_match() {
+	let order = hex::decode(ORDER).unwrap();
+	for bytes in [vec![0u8; 32], order, vec![0xff; 32], vec![1u8; 31], vec![1u8; 33]] {
+		assert!(secp256k1::SecretKey::from_slice(&bytes).is_err());
+		assert_eq!(SecretKey::from_slice(&bytes), Err(Error::InvalidSecretKey));
+	}
+}
+
+#[test]
+fn tweaks_match() {
+	for (key, reference) in key_pairs() {
+		for tweak in digests() {
+			let expected = Scalar::from_be_bytes(tweak)
+				.ok()
+				.and_then(|scalar| reference.add_tweak(&scalar).ok())
+				.map(|sum| sum.secret_bytes());
+			assert_eq!(key.add_tweak(&tweak).ok().map(|sum| *sum.secret_bytes()), expected);
+		}
+	}
+
+	// Adding the negated key gives zero, which is not a valid key.
+	let (key, reference) = &key_pairs()[0];
+	let negated = reference.negate().secret_bytes();
+	assert_eq!(key.add_tweak(&negated), Err(Error::InvalidTweak));
+}
+
+#[tokio::test]
+async fn addresses_and_signer_match() {
+	for (key, reference) in key_pairs() {
+		let expected = l1x_rpc::address_from_verifying_key(
+			&reference.public_key(SECP256K1).serialize_uncompressed(),
+		)
+		.unwrap();
+		assert_eq!(l1x_rpc::get_address_from_private_key(&key).unwrap(), expected.to_string());
+
+		let digest = sha256(key.secret_bytes());
+		let signer = LocalSigner::new(key);
+		let recoverable = l1x_rpc::signer::sign_digest_recoverable(&signer, &digest).await.unwrap();
+		let (recovery_id, compact) = SECP256K1
+			.sign_ecdsa_recoverable(&Message::from_digest(digest), &reference)
+			.serialize_compact();
+		assert_eq!(recoverable[..64], compact);
+		assert_eq!(recoverable[64] as i32, recovery_id.to_i32());
+	}
+}
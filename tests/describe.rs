@@ -0,0 +1,129 @@
+use l1x_rpc::{
+	describe::{format_units, Describe, Field},
+	rpc_model::{self, transaction_v3::Transaction, *},
+	transaction::TransactionTypeV2,
+};
+use serde_json::json;
+
+fn response(transaction: Transaction) -> rpc_model::TransactionV3Response {
+	rpc_model::TransactionV3Response {
+		transaction: Some(rpc_model::TransactionV3 {
+			version: rpc_model::TransactionVersion::V3 as i32,
+			tx_type: transaction.tx_type() as i32,
+			transaction: Some(transaction),
+			nonce: "7".to_owned(),
+			fee_limit: "100".to_owned(),
+			signature: vec![0xaa; 64],
+			verifying_key: vec![0x02; 33],
+			eth_original_transaction: None,
+		}),
+		from: vec![0x44; 20],
+		transaction_hash: vec![0x55; 32],
+		block_hash: vec![0x66; 32],
+		block_number: 12,
+		fee_used: "1500000000000000000".to_owned(),
+		timestamp: 1_700_000_000,
+	}
+}
+
+#[test]
+fn balances_are_formatted() {
+	assert_eq!(format_units(0, 18), "0");
+	assert_eq!(format_units(1_500_000_000_000_000_000, 18), "1.5");
+	assert_eq!(format_units(1, 18), "0.000000000000000001");
+	assert_eq!(format_units(1200, 2), "12");
+	assert_eq!(format_units(u128::MAX, 0), u128::MAX.to_string());
+}
+
+#[test]
+fn function_call_fields() {
+	let description = TransactionTypeV2::SmartContractFunctionCall {
+		contract_instance_address: [0x33; 20],
+		function: b"transfer".to_vec(),
+		arguments: br#"{"to":"abc","amount":5}"#.to_vec(),
+		deposit: 0,
+	}
+	.describe();
+
+	assert_eq!(
+		description.to_json(),
+		json!({
+			"type": "Smart contract function call",
+			"contract": "0x3333333333333333333333333333333333333333",
+			"function": "transfer",
+			"arguments": { "to": "abc", "amount": 5 },
+			"deposit": "0 L1X (0)",
+		})
+	);
+
+	let binary = TransactionTypeV2::SmartContractFunctionCall {
+		contract_instance_address: [0x33; 20],
+		function: vec![0xff, 0x00],
+		arguments: vec![0xfe],
+		deposit: 0,
+	}
+	.describe();
+	assert_eq!(binary.get("function"), Some(&Field::Text("0xff00".to_owned())));
+	assert_eq!(binary.get("arguments"), Some(&Field::Text("0xfe".to_owned())));
+}
+
+#[test]
+fn response_as_text_and_json() {
+	let response = response(Transaction::NativeTokenTransfer(NativeTokenTransfer {
+		address: vec![0x11; 20],
+		amount: "2000000000000000000".to_owned(),
+	}));
+	let description = response.describe();
+
+	let json = description.to_json();
+	assert_eq!(json["type"], "Included transaction");
+	assert_eq!(json["fee_used"], "1.5 L1X (1500000000000000000)");
+	assert_eq!(json["block_number"], "12");
+	assert_eq!(json["transaction"]["nonce"], "7");
+	assert_eq!(
+		json["transaction"]["transaction"]["to"],
+		"0x1111111111111111111111111111111111111111"
+	);
+	assert_eq!(json["transaction"]["transaction"]["amount"], "2 L1X (2000000000000000000)");
+
+	let text = description.to_string();
+	assert!(text.starts_with("Included transaction\n"));
+	assert!(text.contains("\n  fee_used: 1.5 L1X (1500000000000000000)\n"));
+	assert!(text.contains("\n    transaction: Native token transfer\n"));
+	assert!(text.contains("\n      amount: 2 L1X (2000000000000000000)\n"));
+}
+
+#[test]
+fn block_lists_transactions_and_reports_invalid_ones() {
+	let mut invalid = response(Transaction::Stake(Stake {
+		pool_address: vec![0x11; 19],
+		amount: "1".to_owned(),
+	}));
+	invalid.transaction_hash = vec![0x77; 32];
+	let block = BlockV3 {
+		number: "12".to_owned(),
+		hash: "ab".to_owned(),
+		parent_hash: "cd".to_owned(),
+		timestamp: 1_700_000_000,
+		transactions: vec![
+			response(Transaction::Unstake(UnStake {
+				pool_address: vec![0x11; 20],
+				amount: "1".to_owned(),
+			})),
+			invalid,
+		],
+		block_type: BlockType::L1xTokenBlock as i32,
+		cluster_address: "ef".to_owned(),
+		state_hash: "01".to_owned(),
+		block_version: "1".to_owned(),
+		epoch: "3".to_owned(),
+	};
+
+	let json = block.describe().to_json();
+	assert_eq!(json["block_type"], BlockType::L1xTokenBlock.as_str_name());
+	assert_eq!(json["transactions"][0]["transaction"]["transaction"]["type"], "Unstake");
+	assert_eq!(json["transactions"][1]["type"], "Invalid transaction");
+	assert_eq!(json["transactions"][1]["hash"], format!("0x{}", "77".repeat(32)));
+	assert!(json["transactions"][1]["error"].is_string());
+	assert!(block.describe().to_string().contains("transactions: 2 item(s)\n"));
+}
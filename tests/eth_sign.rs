@@ -0,0 +1,122 @@
+use l1x_rpc::{
+	address::Address,
+	crypto,
+	eth_sign::*,
+	signer::{LocalSigner, Signer},
+	transaction::TransactionTypeV2,
+	TXSignPayloadV2, VerifyError,
+};
+use sha3::{Digest, Keccak256};
+
+const PRIVATE_KEY: &str = "6913aeae91daf9d6b5a8e3b4b4e9c0a6a2d0e5a51a9a3e1e8fbcad8c7e5c4a21";
+
+fn keccak(data: &[u8]) -> [u8; 32] {
+	Keccak256::digest(data).into()
+}
+
+fn address_word(address: &str) -> [u8; 32] {
+	let mut word = [0u8; 32];
+	word[12..].copy_from_slice(address.parse::<Address>().unwrap().as_bytes());
+	word
+}
+
+fn payload() -> TXSignPayloadV2 {
+	TXSignPayloadV2 {
+		nonce: "7".to_owned(),
+		transaction_type: TransactionTypeV2::NativeTokenTransfer([0x11; 20], 1000).into(),
+		fee_limit: "100".to_owned(),
+	}
+}
+
+#[test]
+fn eip191_personal_message_hash() {
+	// `hashMessage("Hello World")` of ethers.js and web3.js.
+	assert_eq!(
+		hex::encode(personal_message_hash(b"Hello World")),
+		"a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2"
+	);
+}
+
+#[test]
+fn eip712_mail_example() {
+	let domain = Eip712Domain {
+		name: "Ether Mail".to_owned(),
+		version: "1".to_owned(),
+		chain_id: 1,
+		verifying_contract: Some("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC".parse().unwrap()),
+	};
+	assert_eq!(
+		hex::encode(domain.separator()),
+		"f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+	);
+
+	let person = |name: &str, wallet: &str| {
+		hash_struct(
+			"Person(string name,address wallet)",
+			&[keccak(name.as_bytes()), address_word(wallet)],
+		)
+	};
+	let mail = hash_struct(
+		"Mail(Person from,Person to,string contents)Person(string name,address wallet)",
+		&[
+			person("Cow", "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"),
+			person("Bob", "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"),
+			keccak(b"Hello, Bob!"),
+		],
+	);
+	assert_eq!(
+		hex::encode(mail),
+		"c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+	);
+	assert_eq!(
+		hex::encode(typed_data_digest(&domain, &mail)),
+		"be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+	);
+}
+
+#[tokio::test]
+async fn sign_recover_verify() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let address =
+		l1x_rpc::address_from_verifying_key(&signer.verifying_key().await.unwrap()).unwrap();
+	let other = Address([0x11; 20]);
+	let payload = payload();
+	let domain = Eip712Domain::new(1776);
+
+	let signature = personal_sign_v2(&signer, &payload).await.unwrap();
+	assert!(matches!(signature[64], 27 | 28));
+	assert_eq!(recover_personal_sign_v2(&payload, &signature).unwrap(), address);
+	verify_personal_sign_v2(&payload, &signature, &address).unwrap();
+	assert!(matches!(
+		verify_personal_sign_v2(&payload, &signature, &other),
+		Err(VerifyError::SignatureMismatch)
+	));
+
+	let signature = sign_typed_data_v2(&signer, &domain, &payload).await.unwrap();
+	assert_eq!(recover_typed_data_v2(&domain, &payload, &signature).unwrap(), address);
+	verify_typed_data_v2(&domain, &payload, &signature, &address).unwrap();
+	assert!(matches!(
+		verify_typed_data_v2(&Eip712Domain::new(1), &payload, &signature, &address),
+		Err(VerifyError::SignatureMismatch)
+	));
+}
+
+#[tokio::test]
+async fn recovery_errors_are_kept() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let address =
+		l1x_rpc::address_from_verifying_key(&signer.verifying_key().await.unwrap()).unwrap();
+	let payload = payload();
+	let signature = personal_sign_v2(&signer, &payload).await.unwrap();
+
+	assert!(matches!(
+		verify_personal_sign_v2(&payload, &signature[..64], &address),
+		Err(VerifyError::InvalidSignature(crypto::Error::InvalidSignature))
+	));
+	let mut bad_v = signature;
+	bad_v[64] = 5;
+	assert!(matches!(
+		verify_personal_sign_v2(&payload, &bad_v, &address),
+		Err(VerifyError::InvalidSignature(crypto::Error::InvalidRecoveryId))
+	));
+}
@@ -0,0 +1,127 @@
+use l1x_rpc::{
+	builder::TransactionBuilder,
+	fee::{FeeError, FeePolicy},
+	rpc_model::estimate_fee_request,
+	signer::{LocalSigner, Signer},
+	transaction::TransactionTypeV2,
+};
+use serde_json::{json, Value};
+use std::thread;
+
+const PRIVATE_KEY: &str = "6913aeae91daf9d6b5a8e3b4b4e9c0a6a2d0e5a51a9a3e1e8fbcad8c7e5c4a21";
+
+/// Minimal JSON-RPC node estimating a fee of 1000 for transfers to `0x11..` and 1 otherwise.
+fn spawn_node() -> String {
+	let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+	let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+
+	thread::spawn(move || {
+		for mut request in server.incoming_requests() {
+			let mut content = String::new();
+			request.as_reader().read_to_string(&mut content).unwrap();
+			let params: Value = serde_json::from_str(&content).unwrap();
+			assert_eq!(params["method"], "l1x_estimateFee");
+			let estimate = &params["params"]["request"];
+			assert_eq!(estimate["fee_limit"], "0");
+			let transfer = &estimate["transaction_type"]["NativeTokenTransfer"];
+			let fee = if transfer["address"] == json!(vec![0x11; 20]) { "1000" } else { "1" };
+			let body = json!({ "jsonrpc": "2.0", "result": { "fee": fee }, "id": 1 });
+			request.respond(tiny_http::Response::from_string(body.to_string())).unwrap();
+		}
+	});
+
+	url
+}
+
+#[test]
+fn policies_apply_to_estimate() {
+	assert_eq!(FeePolicy::Fixed(7).apply(1000), Ok(7));
+	assert_eq!(FeePolicy::Estimate.apply(1000), Ok(1000));
+	assert_eq!(FeePolicy::EstimatePlusMargin { percent: 25 }.apply(1000), Ok(1250));
+	assert_eq!(FeePolicy::EstimatePlusMargin { percent: 10 }.apply(1001), Ok(1102));
+	assert_eq!(FeePolicy::EstimatePlusMargin { percent: 0 }.apply(1001), Ok(1001));
+	assert_eq!(
+		FeePolicy::EstimatePlusMargin { percent: 1 }.apply(u128::MAX),
+		Err(FeeError::Overflow { estimate: u128::MAX, percent: 1 })
+	);
+	// Estimates whose product with `percent` does not fit still get their margin.
+	assert_eq!(
+		FeePolicy::EstimatePlusMargin { percent: 50 }.apply(u128::MAX / 2),
+		Ok(u128::MAX / 2 + u128::MAX / 4 + 1)
+	);
+	assert_eq!(FeePolicy::EstimatePlusMargin { percent: 200 }.apply(u128::MAX / 3), Ok(u128::MAX));
+	assert_eq!(
+		FeePolicy::EstimatePlusMargin { percent: 200 }.apply(u128::MAX / 3 + 1),
+		Err(FeeError::Overflow { estimate: u128::MAX / 3 + 1, percent: 200 })
+	);
+	assert_eq!(FeePolicy::EstimateWithCap { cap: 1000 }.apply(1000), Ok(1000));
+	assert_eq!(
+		FeePolicy::EstimateWithCap { cap: 999 }.apply(1000),
+		Err(FeeError::EstimateExceedsCap { estimate: 1000, cap: 999 })
+	);
+}
+
+#[tokio::test]
+async fn estimate_for_domain_transaction() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let url = spawn_node();
+	let transfer = TransactionTypeV2::NativeTokenTransfer([0x11; 20], 5);
+
+	let policy = FeePolicy::EstimatePlusMargin { percent: 50 };
+	let client = reqwest::Client::new().post(&url);
+	let estimate = estimate_fee_request::TransactionType::try_from(transfer.clone()).unwrap();
+	let fee_limit = policy
+		.fee_limit_json_rpc(client, estimate, signer.verifying_key().await.unwrap())
+		.await
+		.unwrap();
+	assert_eq!(fee_limit, 1500);
+
+	let request = TransactionBuilder::try_from(transfer)
+		.unwrap()
+		.nonce(1)
+		.fee_policy(policy)
+		.sign_with_json_rpc(&signer, reqwest::Client::new().post(&url))
+		.await
+		.unwrap();
+	assert_eq!(request.fee_limit, "1500");
+	l1x_rpc::verify_v2(&request).unwrap();
+}
+
+#[tokio::test]
+async fn capped_estimate_refuses_to_sign() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let url = spawn_node();
+	let sign = |to: [u8; 20]| {
+		let (signer, client) = (&signer, reqwest::Client::new().post(&url));
+		async move {
+			TransactionBuilder::new()
+				.transfer(to.into(), 5)
+				.nonce(1)
+				.fee_policy(FeePolicy::EstimateWithCap { cap: 10 })
+				.sign_with_json_rpc(signer, client)
+				.await
+		}
+	};
+
+	assert_eq!(sign([0x22; 20]).await.unwrap().fee_limit, "1");
+	let error = sign([0x11; 20]).await.unwrap_err();
+	assert_eq!(
+		error.downcast_ref::<FeeError>(),
+		Some(&FeeError::EstimateExceedsCap { estimate: 1000, cap: 10 })
+	);
+}
+
+#[tokio::test]
+async fn fixed_policy_needs_no_node() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	// Nothing listens here; a fixed fee limit and nonce never reach the node.
+	let client = reqwest::Client::new().post("http://127.0.0.1:9");
+	let request = TransactionBuilder::new()
+		.stake([0x33; 20].into(), 1)
+		.nonce(1)
+		.fee_policy(FeePolicy::Fixed(42))
+		.sign_with_json_rpc(&signer, client)
+		.await
+		.unwrap();
+	assert_eq!(request.fee_limit, "42");
+}
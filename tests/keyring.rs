@@ -0,0 +1,111 @@
+use l1x_rpc::{
+	crypto::SecretKey,
+	keyring::{KeyRing, KeyRingError},
+	keystore::{write_keystore, Kdf},
+};
+use std::{env, fs};
+use zeroize::Zeroizing;
+
+const PRIVATE_KEY: &str = "6913aeae91daf9d6b5a8e3b4b4e9c0a6a2d0e5a51a9a3e1e8fbcad8c7e5c4a21";
+const PASSWORD: &str = "keyring";
+
+fn key(byte: u8) -> SecretKey {
+	SecretKey::from_slice(&[byte; 32]).unwrap()
+}
+
+fn labels(key_ring: &KeyRing) -> Vec<&str> {
+	key_ring.accounts().map(|(label, _)| label).collect()
+}
+
+#[test]
+fn debug_is_redacted() {
+	let mut key_ring = KeyRing::new();
+	let address = key_ring.insert_hex("alice", Zeroizing::new(PRIVATE_KEY.to_owned())).unwrap();
+
+	let debug = format!("{:?}", key_ring);
+	assert_eq!(debug, format!("{{\"alice\": {:?}}}", address));
+	assert!(!debug.contains(PRIVATE_KEY));
+	assert_eq!(format!("{:?}", key(1)), "SecretKey(<redacted>)");
+	assert_eq!(key(1).to_string(), "<redacted>");
+}
+
+#[test]
+fn labels_and_addresses_are_unique() {
+	let mut key_ring = KeyRing::new();
+	let address = key_ring.insert("alice", key(1)).unwrap();
+
+	assert!(matches!(
+		key_ring.insert("alice", key(2)),
+		Err(KeyRingError::DuplicateLabel(label)) if label == "alice"
+	));
+	assert!(matches!(
+		key_ring.insert("bob", key(1)),
+		Err(KeyRingError::DuplicateAddress(duplicate)) if duplicate == address
+	));
+	assert_eq!(key_ring.len(), 1);
+
+	key_ring.insert("bob", key(2)).unwrap();
+	assert!(key_ring.resolve("alice").is_some());
+	assert!(key_ring.resolve(&address.to_string()).is_some());
+	assert_eq!(key_ring.address_of("alice"), Some(address));
+	assert!(key_ring.remove(&address));
+	assert_eq!(labels(&key_ring), ["bob"]);
+}
+
+#[test]
+fn load_env() {
+	let mut key_ring = KeyRing::new();
+	assert!(matches!(key_ring.load_env(""), Err(KeyRingError::EmptyEnvPrefix)));
+
+	env::set_var("KEYRING_TEST_OK_BOB", hex::encode([2; 32]));
+	env::set_var("KEYRING_TEST_OK_ALICE", format!("0x{}", PRIVATE_KEY));
+	let addresses = key_ring.load_env("KEYRING_TEST_OK_").unwrap();
+	assert_eq!(labels(&key_ring), ["alice", "bob"]);
+	assert_eq!(addresses, key_ring.accounts().map(|(_, address)| address).collect::<Vec<_>>());
+
+	// A malformed key adds none of the batch.
+	env::set_var("KEYRING_TEST_BAD_CAROL", hex::encode([3; 32]));
+	env::set_var("KEYRING_TEST_BAD_DAVE", "not hex");
+	assert!(matches!(
+		key_ring.load_env("KEYRING_TEST_BAD_"),
+		Err(KeyRingError::InvalidKey { label, .. }) if label == "dave"
+	));
+	assert_eq!(key_ring.len(), 2);
+}
+
+#[test]
+fn load_keystore_dir() {
+	let kdf = || Kdf::Pbkdf2 { iterations: 1024 };
+	let dir = tempfile::tempdir().unwrap();
+	let first = write_keystore(dir.path(), &key(1), PASSWORD, kdf()).unwrap();
+	let second = write_keystore(dir.path(), &key(2), PASSWORD, kdf()).unwrap();
+	fs::write(dir.path().join("notes.txt"), "not a keystore").unwrap();
+
+	let mut key_ring = KeyRing::new();
+	let addresses = key_ring.load_keystore_dir(dir.path(), PASSWORD).unwrap();
+	assert_eq!(addresses.len(), 2);
+	let mut stems: Vec<_> = [first, second]
+		.iter()
+		.map(|path| path.file_stem().unwrap().to_str().unwrap().to_owned())
+		.collect();
+	stems.sort();
+	assert_eq!(labels(&key_ring), stems);
+
+	// Loading the same keys again is rejected without adding anything.
+	let mut key_ring = KeyRing::new();
+	key_ring.insert("existing", key(2)).unwrap();
+	assert!(matches!(
+		key_ring.load_keystore_dir(dir.path(), PASSWORD),
+		Err(KeyRingError::DuplicateAddress(_))
+	));
+	assert_eq!(labels(&key_ring), ["existing"]);
+
+	// So is a directory with one undecryptable keystore.
+	let mut key_ring = KeyRing::new();
+	fs::write(dir.path().join("zz-broken.json"), "{}").unwrap();
+	assert!(matches!(
+		key_ring.load_keystore_dir(dir.path(), PASSWORD),
+		Err(KeyRingError::Keystore { path, .. }) if path.ends_with("zz-broken.json")
+	));
+	assert!(key_ring.is_empty());
+}
@@ -0,0 +1,144 @@
+use l1x_rpc::{
+	crypto::SecretKey,
+	keystore::{read_keystore, write_keystore, Kdf, Keystore, KeystoreError},
+};
+
+/// Test vectors of the Web3 Secret Storage Definition, version 3.
+const PASSWORD: &str = "testpassword";
+const PRIVATE_KEY: &str = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+
+const PBKDF2_KEYSTORE: &str = r#"{
+	"crypto": {
+		"cipher": "aes-128-ctr",
+		"cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+		"ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+		"kdf": "pbkdf2",
+		"kdfparams": {
+			"c": 262144,
+			"dklen": 32,
+			"prf": "hmac-sha256",
+			"salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+		},
+		"mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+	},
+	"id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+	"version": 3
+}"#;
+
+const SCRYPT_KEYSTORE: &str = r#"{
+	"crypto": {
+		"cipher": "aes-128-ctr",
+		"cipherparams": { "iv": "83dbcc02d8ccb40e466191a123791e0e" },
+		"ciphertext": "d172bf743a674da9cdad04534d56926ef8358534d458fffccd4e6ad2fbde479c",
+		"kdf": "scrypt",
+		"kdfparams": {
+			"dklen": 32,
+			"n": 262144,
+			"p": 8,
+			"r": 1,
+			"salt": "ab0c7876052600dd703518d6fc3fe8984592145b591fc8fb5c6d43190334ba19"
+		},
+		"mac": "2103ac29920d71da29f15d75b4a16dbe95cfd7ff8faea1056c33131d846e3097"
+	},
+	"id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+	"version": 3
+}"#;
+
+/// The spec vector's password and key under scrypt with `n = 2^10`, `r = 8`, `p = 1`, encrypted
+/// with Python's `hashlib.scrypt`, the `cryptography` package and OpenSSL's Keccak-256.
+const SCRYPT_R8_KEYSTORE: &str = r#"{
+	"crypto": {
+		"cipher": "aes-128-ctr",
+		"cipherparams": { "iv": "83dbcc02d8ccb40e466191a123791e0e" },
+		"ciphertext": "01a05c7f05b697274227d8bd0825a6caa89967e24643426c0fcfa2fb663052d7",
+		"kdf": "scrypt",
+		"kdfparams": {
+			"dklen": 32,
+			"n": 1024,
+			"p": 1,
+			"r": 8,
+			"salt": "ab0c7876052600dd703518d6fc3fe8984592145b591fc8fb5c6d43190334ba19"
+		},
+		"mac": "d60a6540bbdeaa746e4c7b4359c74e4bb0b679bedce5b4d129ad96150d200274"
+	},
+	"id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+	"version": 3
+}"#;
+
+fn keystore(json: &str) -> Keystore {
+	serde_json::from_str(json).unwrap()
+}
+
+#[test]
+fn pbkdf2_spec_vector() {
+	let secret_key = keystore(PBKDF2_KEYSTORE).decrypt(PASSWORD).unwrap();
+	assert_eq!(hex::encode(secret_key.secret_bytes()), PRIVATE_KEY);
+}
+
+#[test]
+fn scrypt_vector() {
+	let secret_key = keystore(SCRYPT_R8_KEYSTORE).decrypt(PASSWORD).unwrap();
+	assert_eq!(hex::encode(secret_key.secret_bytes()), PRIVATE_KEY);
+}
+
+#[test]
+fn scrypt_params_are_bounded() {
+	// The spec vector's `n = 2^18` with `r = 1` breaks RFC 7914's `n < 2^(16 r)`.
+	assert!(matches!(
+		keystore(SCRYPT_KEYSTORE).decrypt(PASSWORD),
+		Err(KeystoreError::InvalidKdfParams(_))
+	));
+	for (from, to) in [
+		(r#""n": 1024"#, r#""n": 1073741824"#),
+		(r#""n": 1024"#, r#""n": 1000"#),
+		(r#""r": 8"#, r#""r": 1024"#),
+		(r#""p": 1,"#, r#""p": 4096,"#),
+		(r#""dklen": 32"#, r#""dklen": 4294967296"#),
+	] {
+		let json = SCRYPT_R8_KEYSTORE.replace(from, to);
+		assert!(matches!(
+			keystore(&json).decrypt(PASSWORD),
+			Err(KeystoreError::InvalidKdfParams(_))
+		));
+	}
+}
+
+#[test]
+fn wrong_password() {
+	assert!(matches!(
+		keystore(PBKDF2_KEYSTORE).decrypt("wrongpassword"),
+		Err(KeystoreError::MacMismatch)
+	));
+}
+
+#[test]
+fn unsupported_kdf_and_prf() {
+	let argon2 = PBKDF2_KEYSTORE.replace(r#""kdf": "pbkdf2""#, r#""kdf": "argon2""#);
+	assert!(serde_json::from_str::<Keystore>(&argon2).is_err());
+
+	let sha512 = PBKDF2_KEYSTORE.replace("hmac-sha256", "hmac-sha512");
+	assert!(matches!(
+		keystore(&sha512).decrypt(PASSWORD),
+		Err(KeystoreError::InvalidKdfParams(reason)) if reason.contains("hmac-sha512")
+	));
+
+	let cipher = PBKDF2_KEYSTORE.replace("aes-128-ctr", "aes-128-cbc");
+	assert!(matches!(
+		keystore(&cipher).decrypt(PASSWORD),
+		Err(KeystoreError::UnsupportedCipher(_))
+	));
+}
+
+#[test]
+fn write_read_round_trip() {
+	let dir = tempfile::tempdir().unwrap();
+	let secret_key = SecretKey::from_hex(PRIVATE_KEY).unwrap();
+	for kdf in [Kdf::Scrypt { log_n: 10, r: 8, p: 1 }, Kdf::Pbkdf2 { iterations: 1024 }] {
+		let path = write_keystore(dir.path(), &secret_key, PASSWORD, kdf).unwrap();
+		let (read, address) = read_keystore(&path, PASSWORD).unwrap();
+		assert_eq!(read.secret_bytes(), secret_key.secret_bytes());
+		assert_eq!(address, l1x_rpc::get_address_from_private_key(&secret_key).unwrap());
+		assert_eq!(path, dir.path().join(format!("{}.json", address)));
+		assert!(matches!(read_keystore(&path, "wrongpassword"), Err(KeystoreError::MacMismatch)));
+	}
+}
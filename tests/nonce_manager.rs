@@ -0,0 +1,151 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use l1x_rpc::{
+	address::Address,
+	nonce::{JsonRpcNonceSource, NonceManager, NonceSource, NonceSync},
+	primitives::Nonce,
+};
+use serde_json::{json, Value};
+use std::{
+	collections::BTreeSet,
+	sync::{Arc, Mutex},
+	thread,
+};
+
+const HOT_WALLET: Address = Address([0x11; 20]);
+
+/// A chain whose account nonce is set by the test.
+#[derive(Clone, Default)]
+struct Chain(Arc<Mutex<Nonce>>);
+
+#[async_trait]
+impl NonceSource for Chain {
+	async fn current_nonce(&self, _address: Address) -> Result<Nonce> {
+		Ok(*self.0.lock().unwrap())
+	}
+}
+
+#[tokio::test]
+async fn concurrent_tasks_get_distinct_nonces() {
+	let chain = Chain::default();
+	*chain.0.lock().unwrap() = 41;
+	let nonces = Arc::new(NonceManager::new(chain));
+
+	let tasks: Vec<_> = (0..100)
+		.map(|_| {
+			let nonces = nonces.clone();
+			tokio::spawn(async move { nonces.next(HOT_WALLET).await.unwrap() })
+		})
+		.collect();
+	let mut handed_out = BTreeSet::new();
+	for task in tasks {
+		assert!(handed_out.insert(task.await.unwrap()));
+	}
+	assert_eq!(handed_out, (42..142).collect());
+	assert_eq!(nonces.next(Address([0x22; 20])).await.unwrap(), 42);
+}
+
+#[tokio::test]
+async fn rejected_nonces_are_reused() {
+	let nonces = NonceManager::new(Chain::default());
+	for expected in 1..=3 {
+		assert_eq!(nonces.next(HOT_WALLET).await.unwrap(), expected);
+	}
+
+	// 3 has nothing after it and is simply reused.
+	nonces.reject(HOT_WALLET, 3);
+	assert!(nonces.gaps(HOT_WALLET).is_empty());
+	assert_eq!(nonces.next(HOT_WALLET).await.unwrap(), 3);
+
+	// 2 blocks 3 until it is filled.
+	nonces.reject(HOT_WALLET, 2);
+	assert_eq!(nonces.gaps(HOT_WALLET), vec![2]);
+	assert_eq!(nonces.next(HOT_WALLET).await.unwrap(), 2);
+	assert!(nonces.gaps(HOT_WALLET).is_empty());
+	assert_eq!(nonces.next(HOT_WALLET).await.unwrap(), 4);
+
+	// Rejecting a nonce that was never handed out changes nothing.
+	nonces.reject(HOT_WALLET, 9);
+	assert!(nonces.gaps(HOT_WALLET).is_empty());
+	assert_eq!(nonces.next(HOT_WALLET).await.unwrap(), 5);
+
+	// A confirmed transaction reported as dropped frees its nonce too.
+	nonces.confirm(HOT_WALLET, 4);
+	assert_eq!(nonces.submitted(HOT_WALLET), vec![4]);
+	nonces.reject(HOT_WALLET, 4);
+	assert!(nonces.submitted(HOT_WALLET).is_empty());
+	assert_eq!(nonces.gaps(HOT_WALLET), vec![4]);
+	assert_eq!(nonces.next(HOT_WALLET).await.unwrap(), 4);
+}
+
+#[tokio::test]
+async fn resync_follows_the_chain() {
+	let chain = Chain::default();
+	let nonces = NonceManager::new(chain.clone());
+	for _ in 1..=4 {
+		nonces.next(HOT_WALLET).await.unwrap();
+	}
+	nonces.reject(HOT_WALLET, 2);
+
+	// Nothing landed yet: the gap stays and new nonces continue.
+	assert_eq!(
+		nonces.resync(HOT_WALLET).await.unwrap(),
+		NonceSync { current: 0, next: 5, gaps: vec![2] }
+	);
+
+	// Another sender used the account up to 9.
+	*chain.0.lock().unwrap() = 9;
+	assert_eq!(
+		nonces.resync(HOT_WALLET).await.unwrap(),
+		NonceSync { current: 9, next: 10, gaps: vec![] }
+	);
+	assert_eq!(nonces.next(HOT_WALLET).await.unwrap(), 10);
+}
+
+#[tokio::test]
+async fn confirmed_but_dropped_nonces_become_gaps() {
+	let chain = Chain::default();
+	let nonces = NonceManager::new(chain.clone());
+	for nonce in 1..=4 {
+		assert_eq!(nonces.next(HOT_WALLET).await.unwrap(), nonce);
+	}
+	for nonce in 1..=3 {
+		nonces.confirm(HOT_WALLET, nonce);
+	}
+	assert_eq!(nonces.submitted(HOT_WALLET), vec![1, 2, 3]);
+
+	// The chain includes 1, drops 2 and 3, and 4 is still awaiting the node's answer.
+	*chain.0.lock().unwrap() = 1;
+	assert_eq!(
+		nonces.resync(HOT_WALLET).await.unwrap(),
+		NonceSync { current: 1, next: 5, gaps: vec![2, 3] }
+	);
+	assert!(nonces.submitted(HOT_WALLET).is_empty());
+	assert_eq!(nonces.next(HOT_WALLET).await.unwrap(), 2);
+	assert_eq!(nonces.next(HOT_WALLET).await.unwrap(), 3);
+	assert_eq!(nonces.next(HOT_WALLET).await.unwrap(), 5);
+}
+
+#[tokio::test]
+async fn json_rpc_source_reads_account_state() {
+	let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+	let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+	thread::spawn(move || {
+		for mut request in server.incoming_requests() {
+			let mut content = String::new();
+			request.as_reader().read_to_string(&mut content).unwrap();
+			let params: Value = serde_json::from_str(&content).unwrap();
+			assert_eq!(params["method"], "l1x_getAccountState");
+			assert_eq!(params["params"]["request"]["address"], HOT_WALLET.to_string());
+			let result = json!({
+				"account_state": { "balance": "0", "nonce": "7", "account_type": 0 }
+			});
+			let body = json!({ "jsonrpc": "2.0", "result": result, "id": 1 });
+			request.respond(tiny_http::Response::from_string(body.to_string())).unwrap();
+		}
+	});
+
+	let nonces = NonceManager::new(JsonRpcNonceSource::new(reqwest::Client::new(), url));
+	assert_eq!(nonces.next(HOT_WALLET).await.unwrap(), 8);
+	assert_eq!(nonces.next(HOT_WALLET).await.unwrap(), 9);
+}
@@ -0,0 +1,126 @@
+use l1x_rpc::{
+	builder::TransactionBuilder,
+	offline::{OfflineError, OfflineTransaction},
+	signer::{LocalSigner, Signer},
+};
+
+const PRIVATE_KEY: &str = "6913aeae91daf9d6b5a8e3b4b4e9c0a6a2d0e5a51a9a3e1e8fbcad8c7e5c4a21";
+
+fn builder() -> TransactionBuilder {
+	TransactionBuilder::new()
+		.call([0x22; 20].into(), "transfer", br#"{"to":"abc"}"#.to_vec())
+		.deposit(5)
+		.nonce(7)
+		.fee_limit(100)
+}
+
+async fn unsigned(signer: &LocalSigner) -> OfflineTransaction {
+	builder()
+		.prepare(signer.verifying_key().await.unwrap())
+		.unwrap()
+		.with_memo("treasury payout #12")
+}
+
+#[tokio::test]
+async fn create_sign_combine() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+
+	// Online -> offline as JSON, offline -> online as binary.
+	let online = unsigned(&signer).await;
+	let json = online.to_json().unwrap();
+	let transaction = OfflineTransaction::from_json(&json).unwrap();
+	assert!(!transaction.is_signed());
+	assert_eq!(transaction.memo, "treasury payout #12");
+	let verifying_key = signer.verifying_key().await.unwrap();
+	assert_eq!(
+		transaction.sender().unwrap(),
+		l1x_rpc::address_from_verifying_key(&verifying_key).unwrap()
+	);
+
+	let signed = transaction.sign(&signer, &online.digest).await.unwrap();
+	let bytes = signed.to_bytes().unwrap();
+	let received = OfflineTransaction::from_bytes(&bytes).unwrap();
+	assert_eq!(received, signed);
+
+	let request = received.into_request().unwrap();
+	assert_eq!(request, builder().sign(&signer).await.unwrap());
+}
+
+#[tokio::test]
+async fn unsigned_binary_round_trip() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let transaction = unsigned(&signer).await;
+	let bytes = transaction.to_bytes().unwrap();
+	assert!(bytes.len() < transaction.to_json().unwrap().len());
+	assert_eq!(OfflineTransaction::from_bytes(&bytes).unwrap(), transaction);
+}
+
+#[tokio::test]
+async fn alterations_are_detected() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let transaction = unsigned(&signer).await;
+
+	// A changed payload no longer matches the digest.
+	let json = transaction.to_json().unwrap().replace("\"100\"", "\"100000\"");
+	assert!(matches!(OfflineTransaction::from_json(&json), Err(OfflineError::DigestMismatch)));
+
+	// Changing the digest along with the payload before signing is refused by the operator's
+	// copy of the digest.
+	let mut replaced = transaction.clone();
+	replaced.payload.fee_limit = "100000".to_owned();
+	replaced.digest = replaced.payload.digest();
+	assert!(matches!(
+		replaced.sign(&signer, &transaction.digest).await,
+		Err(OfflineError::UnexpectedDigest)
+	));
+
+	// Changing both after signing breaks the signature.
+	let mut signed = transaction.clone().sign(&signer, &transaction.digest).await.unwrap();
+	signed.payload.fee_limit = "100000".to_owned();
+	signed.digest = signed.payload.digest();
+	assert!(matches!(signed.into_request(), Err(OfflineError::InvalidSignature(_))));
+
+	let mut bytes = transaction.to_bytes().unwrap();
+	bytes[0] = b'X';
+	assert!(matches!(OfflineTransaction::from_bytes(&bytes), Err(OfflineError::BadMagic)));
+
+	let future = OfflineTransaction { version: 2, ..transaction };
+	assert!(matches!(future.validate(), Err(OfflineError::UnsupportedVersion(2))));
+}
+
+#[tokio::test]
+async fn non_canonical_payloads_are_refused() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	for nonce in ["007", "+7"] {
+		let mut transaction = unsigned(&signer).await;
+		transaction.payload.nonce = nonce.to_owned();
+		transaction.digest = transaction.payload.digest();
+
+		let json = transaction.to_json().unwrap();
+		assert!(matches!(OfflineTransaction::from_json(&json), Err(OfflineError::NonCanonical)));
+		let bytes = transaction.to_bytes().unwrap();
+		assert!(matches!(OfflineTransaction::from_bytes(&bytes), Err(OfflineError::NonCanonical)));
+		let digest = transaction.digest;
+		assert!(matches!(
+			transaction.sign(&signer, &digest).await,
+			Err(OfflineError::NonCanonical)
+		));
+	}
+}
+
+#[tokio::test]
+async fn signing_misuse() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let other = LocalSigner::from_hex(&"11".repeat(32)).unwrap();
+	let transaction = unsigned(&signer).await;
+	let digest = transaction.digest;
+
+	assert!(matches!(transaction.clone().into_request(), Err(OfflineError::NotSigned)));
+	assert!(matches!(
+		transaction.clone().sign(&other, &digest).await,
+		Err(OfflineError::WrongSigner { .. })
+	));
+	let signed = transaction.sign(&signer, &digest).await.unwrap();
+	assert!(matches!(signed.sign(&signer, &digest).await, Err(OfflineError::AlreadySigned)));
+	assert!(builder().nonce(1).transfer([0x11; 20].into(), 1).prepare(vec![2; 33]).is_err());
+}
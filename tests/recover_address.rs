@@ -0,0 +1,123 @@
+use l1x_rpc::{
+	address::Address,
+	rpc_model::{submit_transaction_request_v2::TransactionType, *},
+	signer::{LocalSigner, Signer},
+	transaction::{self, TransactionTypeV2, TransactionVersion},
+	TXSignPayloadV2,
+};
+use rlp::RlpStream;
+use sha3::{Digest, Keccak256};
+
+const PRIVATE_KEY: &str = "6913aeae91daf9d6b5a8e3b4b4e9c0a6a2d0e5a51a9a3e1e8fbcad8c7e5c4a21";
+
+/// EIP-155 example transaction signed with the key `0x4646..46`.
+const EIP155_TX: &str = "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+const EIP155_SENDER: &str = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F";
+
+fn address() -> Address {
+	l1x_rpc::get_address_from_privkey_str(PRIVATE_KEY).unwrap().parse().unwrap()
+}
+
+fn transfer() -> TransactionType {
+	TransactionType::NativeTokenTransfer(NativeTokenTransfer {
+		address: vec![0x11; 20],
+		amount: "1000".to_owned(),
+	})
+}
+
+fn transfer_v3(signature: Vec<u8>, verifying_key: Vec<u8>) -> transaction::TransactionV3 {
+	transaction::TransactionV3 {
+		version: TransactionVersion::V3,
+		nonce: 7,
+		transaction_type: TransactionTypeV2::NativeTokenTransfer([0x11; 20], 1000),
+		fee_limit: 100,
+		signature,
+		verifying_key,
+		eth_original_transaction: None,
+	}
+}
+
+#[tokio::test]
+async fn recoverable_signature_recovers_signer_address() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let recoverable = l1x_rpc::sign_v2_recoverable(&signer, transfer(), 100, 7).await.unwrap();
+	let compact = l1x_rpc::sign_v2(&signer, transfer(), 100, 7).await.unwrap();
+	assert_eq!(recoverable[..64], compact[..]);
+
+	let payload = TXSignPayloadV2 {
+		nonce: "7".to_owned(),
+		transaction_type: TransactionTypeV2::NativeTokenTransfer([0x11; 20], 1000).into(),
+		fee_limit: "100".to_owned(),
+	};
+	assert_eq!(l1x_rpc::recover_address(&payload, &recoverable).unwrap(), address());
+
+	let tampered = TXSignPayloadV2 { fee_limit: "101".to_owned(), ..payload };
+	assert_ne!(l1x_rpc::recover_address(&tampered, &recoverable).unwrap(), address());
+	assert!(l1x_rpc::recover_address(&tampered, &compact).is_err());
+}
+
+#[tokio::test]
+async fn native_transaction_sender() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let verifying_key = signer.verifying_key().await.unwrap();
+
+	let compact = l1x_rpc::sign_v2(&signer, transfer(), 100, 7).await.unwrap();
+	assert_eq!(transfer_v3(compact.clone(), verifying_key).recover_sender().unwrap(), address());
+
+	let recoverable = l1x_rpc::sign_v2_recoverable(&signer, transfer(), 100, 7).await.unwrap();
+	assert_eq!(transfer_v3(recoverable.to_vec(), vec![]).recover_sender().unwrap(), address());
+
+	// A compact signature cannot be attributed to a claimed key it does not verify under.
+	let other_key = LocalSigner::from_hex(&"11".repeat(32)).unwrap().verifying_key().await.unwrap();
+	assert!(transfer_v3(compact, other_key).recover_sender().is_err());
+}
+
+#[test]
+fn eip155_transaction_sender() {
+	let eth_tx = hex::decode(EIP155_TX).unwrap();
+	assert_eq!(
+		l1x_rpc::eth_transaction::recover_sender(&eth_tx).unwrap(),
+		EIP155_SENDER.parse().unwrap()
+	);
+
+	let transaction = transaction::TransactionV3 {
+		eth_original_transaction: Some(eth_tx),
+		..transfer_v3(vec![], vec![])
+	};
+	assert_eq!(transaction.recover_sender().unwrap(), EIP155_SENDER.parse().unwrap());
+}
+
+#[tokio::test]
+async fn eip1559_transaction_sender() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let fields = |stream: &mut RlpStream| {
+		stream.append(&1u64); // chain id
+		stream.append(&3u64); // nonce
+		stream.append(&1_000_000_000u64); // max priority fee per gas
+		stream.append(&20_000_000_000u64); // max fee per gas
+		stream.append(&21_000u64); // gas limit
+		stream.append(&vec![0x35u8; 20]); // to
+		stream.append(&1_000_000_000_000_000u64); // value
+		stream.append_empty_data(); // data
+		stream.begin_list(0); // access list
+	};
+
+	let mut unsigned = RlpStream::new_list(9);
+	fields(&mut unsigned);
+	let mut preimage = vec![2u8];
+	preimage.extend_from_slice(&unsigned.out());
+	let digest: [u8; 32] = Keccak256::digest(&preimage).into();
+	let signature = l1x_rpc::signer::sign_digest_recoverable(&signer, &digest).await.unwrap();
+
+	let mut signed = RlpStream::new_list(12);
+	fields(&mut signed);
+	signed.append(&signature[64]);
+	signed.append(&signature[..32].to_vec());
+	signed.append(&signature[32..64].to_vec());
+	let mut raw = vec![2u8];
+	raw.extend_from_slice(&signed.out());
+
+	assert_eq!(l1x_rpc::eth_transaction::recover_sender(&raw).unwrap(), address());
+	raw[1 + 3] ^= 1;
+	assert_ne!(l1x_rpc::eth_transaction::recover_sender(&raw).ok(), Some(address()));
+}
@@ -0,0 +1,96 @@
+use l1x_rpc::{
+	crypto::SecretKey,
+	rpc_model::{submit_transaction_request_v2::TransactionType, *},
+	signer::{LocalSigner, RemoteSigner, Signer},
+};
+use serde_json::{json, Value};
+use std::thread;
+
+const PRIVATE_KEY: &str = "6913aeae91daf9d6b5a8e3b4b4e9c0a6a2d0e5a51a9a3e1e8fbcad8c7e5c4a21";
+
+/// Minimal stand-in for a signing service publishing `secret_key` as "hot" but signing with
+/// `signing_key`.
+fn spawn_signing_service(secret_key: SecretKey, signing_key: SecretKey) -> String {
+	let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+	let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+	let verifying_key = hex::encode(secret_key.public_key().serialize());
+	let signing_verifying_key = hex::encode(signing_key.public_key().serialize());
+
+	thread::spawn(move || {
+		for mut request in server.incoming_requests() {
+			let body = match (request.method(), request.url()) {
+				(tiny_http::Method::Get, "/keys/hot") => json!({ "verifying_key": verifying_key }),
+				(tiny_http::Method::Post, "/sign") => {
+					let mut content = String::new();
+					request.as_reader().read_to_string(&mut content).unwrap();
+					let params: Value = serde_json::from_str(&content).unwrap();
+					assert_eq!(params["key_id"], "hot");
+					let digest = hex::decode(params["digest"].as_str().unwrap()).unwrap();
+					let sig = signing_key.sign_digest(&digest.try_into().unwrap());
+					json!({
+						"signature": hex::encode(sig),
+						"verifying_key": signing_verifying_key,
+					})
+				},
+				_ => {
+					request.respond(tiny_http::Response::empty(404)).unwrap();
+					continue
+				},
+			};
+			request.respond(tiny_http::Response::from_string(body.to_string())).unwrap();
+		}
+	});
+
+	url
+}
+
+fn transfer() -> TransactionType {
+	TransactionType::NativeTokenTransfer(NativeTokenTransfer {
+		address: vec![0x11; 20],
+		amount: "1000".to_owned(),
+	})
+}
+
+#[tokio::test]
+async fn remote_signer_matches_local_signer() {
+	let secret_key = SecretKey::from_slice(&hex::decode(PRIVATE_KEY).unwrap()).unwrap();
+	let url = spawn_signing_service(secret_key.clone(), secret_key.clone());
+	let remote = RemoteSigner::new(reqwest::Client::new(), url, "hot");
+	let local = LocalSigner::new(secret_key);
+
+	assert_eq!(remote.verifying_key().await.unwrap(), local.verifying_key().await.unwrap());
+
+	let remote_sig = l1x_rpc::sign_v2(&remote, transfer(), 100, 7).await.unwrap();
+	let local_sig = l1x_rpc::sign_v2(&local, transfer(), 100, 7).await.unwrap();
+	assert_eq!(remote_sig, local_sig);
+
+	let request = SubmitTransactionRequestV2 {
+		nonce: "7".to_owned(),
+		transaction_type: Some(transfer()),
+		fee_limit: "100".to_owned(),
+		signature: remote_sig,
+		verifying_key: remote.verifying_key().await.unwrap(),
+	};
+	l1x_rpc::verify_v2(&request).unwrap();
+}
+
+#[tokio::test]
+async fn remote_signer_rejects_unknown_key() {
+	let secret_key = SecretKey::from_slice(&hex::decode(PRIVATE_KEY).unwrap()).unwrap();
+	let url = spawn_signing_service(secret_key.clone(), secret_key);
+	let remote = RemoteSigner::new(reqwest::Client::new(), url, "cold");
+
+	assert!(remote.verifying_key().await.is_err());
+}
+
+#[tokio::test]
+async fn remote_signer_rejects_signature_from_unpublished_key() {
+	let secret_key = SecretKey::from_slice(&hex::decode(PRIVATE_KEY).unwrap()).unwrap();
+	let other_key = SecretKey::from_slice(&[0x11; 32]).unwrap();
+	let url = spawn_signing_service(secret_key, other_key);
+	let remote = RemoteSigner::new(reqwest::Client::new(), url, "hot");
+
+	// The signature is valid for the key the service returns, but that is not the published key.
+	let error = l1x_rpc::sign_v2(&remote, transfer(), 100, 7).await.unwrap_err();
+	assert!(error.to_string().contains("not the key published for hot"), "{}", error);
+}
@@ -0,0 +1,279 @@
+//! Golden vectors for the canonical sign payload encoding. A failure here means every client
+//! in the field would produce signatures the node no longer accepts, or the other way round.
+
+use l1x_rpc::{
+	signer::LocalSigner,
+	transaction::{AccessType, ContractType, TransactionType, TransactionTypeV2},
+	TXSignPayload, TXSignPayloadV2,
+};
+
+const PRIVATE_KEY: &str = "6913aeae91daf9d6b5a8e3b4b4e9c0a6a2d0e5a51a9a3e1e8fbcad8c7e5c4a21";
+
+const A: [u8; 20] = [0x11; 20];
+const B: [u8; 20] = [0x22; 20];
+const A_JSON: &str = "[17,17,17,17,17,17,17,17,17,17,17,17,17,17,17,17,17,17,17,17]";
+const B_JSON: &str = "[34,34,34,34,34,34,34,34,34,34,34,34,34,34,34,34,34,34,34,34]";
+
+fn v2(transaction_type: TransactionTypeV2) -> TXSignPayloadV2 {
+	TXSignPayloadV2 {
+		nonce: "42".to_owned(),
+		transaction_type: transaction_type.into(),
+		fee_limit: "1000000".to_owned(),
+	}
+}
+
+fn assert_v2(transaction_type: TransactionTypeV2, expected_transaction_type: &str) {
+	let payload = v2(transaction_type);
+	let expected = format!(
+		r#"{{"nonce":"42","transaction_type":{},"fee_limit":"1000000"}}"#,
+		expected_transaction_type
+	);
+	assert_eq!(String::from_utf8(payload.signing_bytes()).unwrap(), expected);
+	// The encoding is also what serde produced before the dedicated encoder existed.
+	assert_eq!(payload.signing_bytes(), serde_json::to_vec(&payload).unwrap());
+}
+
+#[test]
+fn native_token_transfer() {
+	assert_v2(
+		TransactionTypeV2::NativeTokenTransfer(A, 340282366920938463463374607431768211455),
+		&format!(
+			r#"{{"NativeTokenTransfer":[{},"340282366920938463463374607431768211455"]}}"#,
+			A_JSON
+		),
+	);
+}
+
+#[test]
+fn smart_contract_deployment() {
+	for (access_type, access_name) in [
+		(AccessType::PRIVATE, "PRIVATE"),
+		(AccessType::PUBLIC, "PUBLIC"),
+		(AccessType::RESTICTED, "RESTICTED"),
+	] {
+		for (contract_type, contract_name) in [
+			(ContractType::L1XVM, "L1XVM"),
+			(ContractType::EVM, "EVM"),
+			(ContractType::XTALK, "XTALK"),
+		] {
+			assert_v2(
+				TransactionTypeV2::SmartContractDeployment {
+					access_type: access_type.clone(),
+					contract_type,
+					contract_code: vec![0, 97, 115, 109],
+					deposit: 0,
+					salt: vec![1, 2],
+				},
+				&format!(
+					r#"{{"SmartContractDeployment":{{"access_type":"{}","contract_type":"{}","contract_code":[0,97,115,109],"deposit":"0","salt":[1,2]}}}}"#,
+					access_name, contract_name
+				),
+			);
+		}
+	}
+}
+
+#[test]
+fn smart_contract_init() {
+	assert_v2(
+		TransactionTypeV2::SmartContractInit {
+			contract_code_address: A,
+			arguments: b"{}".to_vec(),
+			deposit: 5,
+		},
+		&format!(
+			r#"{{"SmartContractInit":{{"contract_code_address":{},"arguments":[123,125],"deposit":"5"}}}}"#,
+			A_JSON
+		),
+	);
+}
+
+#[test]
+fn smart_contract_function_call() {
+	assert_v2(
+		TransactionTypeV2::SmartContractFunctionCall {
+			contract_instance_address: A,
+			function: b"get".to_vec(),
+			arguments: vec![],
+			deposit: 1_000_000_000_000_000_000,
+		},
+		&format!(
+			r#"{{"SmartContractFunctionCall":{{"contract_instance_address":{},"function":[103,101,116],"arguments":[],"deposit":"1000000000000000000"}}}}"#,
+			A_JSON
+		),
+	);
+}
+
+#[test]
+fn stake_unstake_and_pool_contract() {
+	assert_v2(
+		TransactionTypeV2::Stake { pool_address: A, amount: 7 },
+		&format!(r#"{{"Stake":{{"pool_address":{},"amount":7}}}}"#, A_JSON),
+	);
+	assert_v2(
+		TransactionTypeV2::UnStake { pool_address: A, amount: 7 },
+		&format!(r#"{{"UnStake":{{"pool_address":{},"amount":7}}}}"#, A_JSON),
+	);
+	assert_v2(
+		TransactionTypeV2::StakingPoolContract { pool_address: A, contract_instance_address: B },
+		&format!(
+			r#"{{"StakingPoolContract":{{"pool_address":{},"contract_instance_address":{}}}}}"#,
+			A_JSON, B_JSON
+		),
+	);
+}
+
+fn staking_pool(mask: u8) -> TransactionTypeV2 {
+	TransactionTypeV2::CreateStakingPool {
+		contract_instance_address: (mask & 1 != 0).then_some([0x33; 20]),
+		min_stake: (mask & 2 != 0).then_some(100),
+		max_stake: (mask & 4 != 0).then_some(u128::MAX),
+		min_pool_balance: (mask & 8 != 0).then_some(1_000),
+		max_pool_balance: (mask & 16 != 0).then_some(1_000_000_000_000_000_000_000),
+		staking_period: (mask & 32 != 0).then_some(86_400),
+	}
+}
+
+/// SHA-256 of the signing bytes of `staking_pool(mask)`, indexed by mask.
+const STAKING_POOL_DIGESTS: [&str; 64] = [
+	"c7d191f12de6a5b87dbf2b20ec9f71e222d586b41aa2de3f956f6a97e64e7000",
+	"638cb2e108453f3f9f9a1118c5b2a2f9eac85a9fbddcd7f689ed880697873f1e",
+	"bdecb6a38da2c811ba6329f8f5d7f476c83347dc156812a518cc3c14706c11d1",
+	"7d5a7d6c9e6cb4ee70e810234b990a15261dc8963106e47ffdb6a99a8cdc10e6",
+	"59da6da0e9e96ae9d20eb21b445767ac350130157ea878e3510a5af15ee01722",
+	"e7a2d8d3d7ed6a86a38fef54eb999833711573e2cc32620c07286fd335e128f3",
+	"56994e11c725aba50e2ee18b436b66741120d3c157302efb0a26bbd49aedc6ee",
+	"9eb6ae0c4a6009723826a0d61dc4973e1fe9ea7b3e4be0ee1a757bffef18c9ef",
+	"d24365ed4e3cba5b82fe77f072c239eaa02c175c7b269a4e2e60157f2cd969ca",
+	"190cf03cbd9cff8c502136fa4d770b8fe2587d1872cc73db120360885fbe0acc",
+	"1cb73f78a6063e9e79f1578ddfcf9a6ffbb559b96527421c4f2a9baceb33dd66",
+	"8493b5bb6af6c5a3c3b64e073c368d95f277a79080958367aade3948cc70f748",
+	"e0b71497463c8c6135c99ff4e82d045e1b9a55cdd148e7b58bd163087c51ce54",
+	"5c96b280ca20fa8be77098fe8a6df751cb78fc47649543228e6805a0f8765edc",
+	"26fb8405236dcc33d8b65c5a17cec7d3f8278a757f84870ad434d98e5412adda",
+	"d23b143caba9fa115dd90c4ba56266a6a20ef69a690e0a83f94983ef179b6bda",
+	"551732fa17ea562800b0345c55f70b2029922fd310a506268bbd115e23691c9c",
+	"b1368b9aec18ac6bb715f4be5d86bc26700a8d5b512b07e2727e1db55eed653b",
+	"fce2b659dd3b9707cdb86346b931e8ea7d3231ead10c9e21b4f17303cd5f1245",
+	"3c5fa52e8345a560b472e79bbae8321ed4f018d27fa89a68fcb62ef8ca999252",
+	"e468ea49eeeedaf4192c90649ffb5c9084f9bc0ef23747c90c62ce24fb0d5f8e",
+	"7d4228e3a0dbad067e87c448b400c4928adee35225128fb0eda727c67ab521d9",
+	"74d7a7c9cd72f23bcba36ed5efacc520a11eaa556027472338e39db53b1a94c2",
+	"ffa8c59749688a25f8002b856cf00179f943bf64c544a693b79a647a3f423196",
+	"31890331e15cbb60a116ac32cb0619060c82b606dc1517bf4c46d2a00c4d35ae",
+	"b3fcee96e67be35a92f22e98e075d6e68e4b3560a32164fb56b94026441ffe86",
+	"74eaae6a24eb1eaa88679eb47ccf53e19f1235314dc958d83474f7be00fb4a4f",
+	"9a716eef52609812fe17d294efd7c15eca317b143dffe62d09380a1edf28e5b4",
+	"8b2184a40a734eff2c26581868a74d24e6dac52a98963ab7ab149f94993659d2",
+	"3b50ea6493ca9f96cce4568b8fc5177911f6ef1e704ab95ad70eb03c57fadace",
+	"9ed9452ee1103f78f1618a7ac543dffd707450541143afebdc562df39a068a2c",
+	"647f3a80bb64481df09784b35fd2f7faabd0a66899b9f1725bf398886fdbab7a",
+	"d634a78e43d0b6919119d63d17e03d9840f48236fe5fda87b9ba0c1e62bf6559",
+	"dd01da63450988da70bf9c6b74d12876086caef4ea0471351a037144a4f37f07",
+	"3d12b01410cb7b460036efba50f6af4e97555d5fd7a43dff2b6981837d3654ab",
+	"6e5a241872cb2944e2647bbfbd58b659450d8d57b2090d80a8d382a59d83c0ff",
+	"7a40582ca31abf7b16a1b31eb31ac953734e3d8d0fd283cad624a9f67e8557af",
+	"a69323100ba183e5f2be75a47ef5bb1521321ac0abf54db19e2f73d686696b6a",
+	"62e3c38ebf41541b39723a5ace4d5fe4e8f9a1baaac447771816df1436b1abd6",
+	"2b9acb57295ea1764abaec722518d298de74b9a57f8c5272faa3660f391823ff",
+	"2ae76f335bbfca262af53f3efb2fc20352552b3093633cd7a980910ab54e015e",
+	"bd31ac42f1e48fd0ae9a61393c6b25b51f6473cbf9a7690feb54e922aa3b32ec",
+	"ab711432f9216ab19cb4611270867630b19b5c82bb0a8709c946f15375ce10c9",
+	"81c5c36619083588c162e8aa5019172143b0e1946249d4966be76abce8930ba7",
+	"10592e6f9dba7068bdc8519c9b34d872633c63541f3c0a67588cc9bb68c07361",
+	"e3265a31734e3d784909d4a261b0e9a474769dab8005ec2de454d5bd81d4db4c",
+	"024241ef8c4c20424c2cc91098ae753ee5cc121484d922f70f344bf544463743",
+	"fdde58f0b8b02229c7f99e23c627125f98f29e2e6c8b812c74a2da2befbe4bfe",
+	"3afe68fe29f238a2da5aacf92aa0cac0bb16725dc88c9b50cd13e9519d3fe973",
+	"bb047d616d3f359608382538509d5039e891845e34bfb5a50e3477b7eb11a4f5",
+	"99fca85ec13a615e48243b988f20b81d2dd520e2acd9fbada7135722704b98ef",
+	"bea51e3e69962f6e349100631483c1d01830eb85833b01a559f83b64c1860dd7",
+	"f92ee95d98a788f3789ff621e87cb39977af5cd6eadb167f52a68f0e544b347e",
+	"9d5f5eda31b0c3e0c941a8a9583e8b6520014aba22c5e3bdf52b761e43051959",
+	"a0686f520a99cfd38c5e9ccea6a7ca8da3f7dfde901c6151224b9170e77a8dbf",
+	"55340a677511a1c692995c77961ffe5de4ad88951afa9765478344d5d9be1fb5",
+	"2b7b25109fef3b6c4f64d60043dca2fa725ca02929b9205c3cc45492805c9859",
+	"7f464065abaec048cf4190e73f2a41d2a5bea4e7d09c2b9c1bb66fd960bd79b5",
+	"c90316bbb74569ed2546f86a3645f077e818536b09b0359d1ae33058e8a90f29",
+	"551860ae40432a5f308ca1fa9843b36fb0ec5d8d18213e057e39496bb9b4b986",
+	"c10c8b65d2dc9d3ac2f220a21a4d838ba00566c9f33bae78e92f9c1e5328c906",
+	"8781fe341db023cbe5b58f7e2b2f4af315768ec551608e03291dd60a6d2eb445",
+	"4944bded7ac2a8cf858dc6e001ea59c82fa7f402569e079cc7cceb44cc9e2896",
+	"d4f0fb431de3bd32d97c4230e668f881163e668cb8a9782cd2fa51faeffa92fb",
+];
+
+#[test]
+fn create_staking_pool_every_optional_field_combination() {
+	assert_v2(
+		staking_pool(0),
+		r#"{"CreateStakingPool":{"contract_instance_address":null,"min_stake":null,"max_stake":null,"min_pool_balance":null,"max_pool_balance":null,"staking_period":null}}"#,
+	);
+	assert_v2(
+		staking_pool(63),
+		r#"{"CreateStakingPool":{"contract_instance_address":[51,51,51,51,51,51,51,51,51,51,51,51,51,51,51,51,51,51,51,51],"min_stake":100,"max_stake":340282366920938463463374607431768211455,"min_pool_balance":1000,"max_pool_balance":1000000000000000000000,"staking_period":86400}}"#,
+	);
+
+	for (mask, expected) in STAKING_POOL_DIGESTS.iter().enumerate() {
+		let payload = v2(staking_pool(mask as u8));
+		assert_eq!(hex::encode(payload.digest()), *expected, "mask {:06b}", mask);
+		assert_eq!(payload.signing_bytes(), serde_json::to_vec(&payload).unwrap());
+	}
+}
+
+#[test]
+fn v1_payloads() {
+	let cases = [
+		(
+			TransactionType::NativeTokenTransfer(A, 10),
+			format!(r#"{{"NativeTokenTransfer":[{},10]}}"#, A_JSON),
+		),
+		(
+			TransactionType::SmartContractDeployment {
+				access_type: AccessType::PUBLIC,
+				contract_type: ContractType::EVM,
+				contract_code: vec![96],
+				value: 3,
+				salt: vec![],
+			},
+			r#"{"SmartContractDeployment":{"access_type":"PUBLIC","contract_type":"EVM","contract_code":[96],"value":3,"salt":[]}}"#.to_owned(),
+		),
+		(
+			TransactionType::SmartContractInit(A, vec![1]),
+			format!(r#"{{"SmartContractInit":[{},[1]]}}"#, A_JSON),
+		),
+		(
+			TransactionType::SmartContractFunctionCall {
+				contract_instance_address: A,
+				function: vec![102],
+				arguments: vec![2],
+			},
+			format!(
+				r#"{{"SmartContractFunctionCall":{{"contract_instance_address":{},"function":[102],"arguments":[2]}}}}"#,
+				A_JSON
+			),
+		),
+	];
+	for (transaction_type, expected_transaction_type) in cases {
+		let payload = TXSignPayload { nonce: 1, transaction_type, fee_limit: 2 };
+		let expected = format!(
+			r#"{{"nonce":1,"transaction_type":{},"fee_limit":2}}"#,
+			expected_transaction_type
+		);
+		assert_eq!(String::from_utf8(payload.signing_bytes()).unwrap(), expected);
+		assert_eq!(payload.signing_bytes(), serde_json::to_vec(&payload).unwrap());
+	}
+}
+
+#[tokio::test]
+async fn signature_over_canonical_bytes() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let transaction_type =
+		l1x_rpc::rpc_model::submit_transaction_request_v2::TransactionType::Stake(
+			l1x_rpc::rpc_model::Stake { pool_address: A.to_vec(), amount: "7".to_owned() },
+		);
+	let signature = l1x_rpc::sign_v2(&signer, transaction_type, 1_000_000, 42).await.unwrap();
+	assert_eq!(
+		hex::encode(signature),
+		"ca8da98714724533f296b50596e1684f093aadd380a0aa5982061da2baa1d87b40a752b1031844661f55cdfa5dec4121bc2a5bbf7302c5ed35b14d29bfd634b0"
+	);
+}
@@ -0,0 +1,128 @@
+use l1x_rpc::{
+	builder::TransactionBuilder,
+	rpc_model::SmartContractReadOnlyCallStatus,
+	signer::{LocalSigner, Signer},
+	simulate::{simulate_json_rpc, submit_simulated_json_rpc, SimulationFailed},
+	transaction::TransactionTypeV2,
+};
+use serde_json::{json, Value};
+use std::{
+	sync::mpsc::{self, Receiver},
+	thread,
+};
+
+const PRIVATE_KEY: &str = "6913aeae91daf9d6b5a8e3b4b4e9c0a6a2d0e5a51a9a3e1e8fbcad8c7e5c4a21";
+
+/// Minimal JSON-RPC node where `revert` fails and other functions return `{"balance":"10"}`.
+/// The methods called are sent to the returned receiver.
+fn spawn_node() -> (String, Receiver<String>) {
+	let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+	let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+	let (methods, received) = mpsc::channel();
+
+	thread::spawn(move || {
+		for mut request in server.incoming_requests() {
+			let mut content = String::new();
+			request.as_reader().read_to_string(&mut content).unwrap();
+			let params: Value = serde_json::from_str(&content).unwrap();
+			let method = params["method"].as_str().unwrap().to_owned();
+			let result = match method.as_str() {
+				"l1x_smartContractReadOnlyCall" => {
+					let call = &params["params"]["request"]["call"];
+					assert_eq!(call["contract_address"], json!(vec![0x22; 20]));
+					if call["function_name"] == json!(b"revert".to_vec()) {
+						let status = SmartContractReadOnlyCallStatus::Failed;
+						json!({ "status": status as i32, "result": [] })
+					} else {
+						json!({
+							"status": SmartContractReadOnlyCallStatus::Succeeded as i32,
+							"result": br#"{"balance":"10"}"#.to_vec(),
+						})
+					}
+				},
+				"l1x_estimateFee" => json!({ "fee": "250" }),
+				"l1x_submitTransactionV2" => json!({ "hash": "abcd", "contract_address": null }),
+				method => panic!("unexpected method {}", method),
+			};
+			methods.send(method).unwrap();
+			let body = json!({ "jsonrpc": "2.0", "result": result, "id": 1 });
+			request.respond(tiny_http::Response::from_string(body.to_string())).unwrap();
+		}
+	});
+
+	(url, received)
+}
+
+fn call(function: &str) -> TransactionTypeV2 {
+	TransactionTypeV2::SmartContractFunctionCall {
+		contract_instance_address: [0x22; 20],
+		function: function.as_bytes().to_vec(),
+		arguments: b"{}".to_vec(),
+		deposit: 0,
+	}
+}
+
+#[tokio::test]
+async fn simulation_decodes_result_and_estimates_fee() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let (url, _methods) = spawn_node();
+	let client = reqwest::Client::new();
+	let verifying_key = signer.verifying_key().await.unwrap();
+
+	let simulation =
+		simulate_json_rpc(client.post(&url), call("balance_of"), verifying_key.clone())
+			.await
+			.unwrap();
+	assert!(simulation.succeeded());
+	assert_eq!(simulation.decoded, Some(json!({ "balance": "10" })));
+	assert_eq!(simulation.fee_estimate, 250);
+
+	let failed = simulate_json_rpc(client.post(&url), call("revert"), verifying_key.clone())
+		.await
+		.unwrap();
+	assert_eq!(failed.status, SmartContractReadOnlyCallStatus::Failed);
+	assert_eq!(failed.decoded, None);
+
+	let transfer = TransactionTypeV2::NativeTokenTransfer([0x11; 20], 1);
+	assert!(simulate_json_rpc(client.post(&url), transfer, verifying_key).await.is_err());
+}
+
+#[tokio::test]
+async fn failed_simulation_is_not_submitted() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let (url, methods) = spawn_node();
+	let client = reqwest::Client::new();
+	let request = |function| {
+		TransactionBuilder::try_from(call(function))
+			.unwrap()
+			.nonce(1)
+			.fee_limit(300)
+			.sign(&signer)
+	};
+
+	let error = submit_simulated_json_rpc(client.post(&url), request("revert").await.unwrap())
+		.await
+		.unwrap_err();
+	let SimulationFailed(simulation) = error.downcast_ref::<SimulationFailed>().unwrap();
+	assert!(!simulation.succeeded());
+	let called: Vec<_> = methods.try_iter().collect();
+	assert_eq!(called, ["l1x_smartContractReadOnlyCall", "l1x_estimateFee"]);
+
+	let response = submit_simulated_json_rpc(client.post(&url), request("get").await.unwrap())
+		.await
+		.unwrap();
+	assert_eq!(response.hash, "abcd");
+	assert_eq!(methods.try_iter().last().as_deref(), Some("l1x_submitTransactionV2"));
+
+	// Transactions other than contract calls are submitted without a simulation.
+	let stake = TransactionBuilder::new()
+		.stake([0x33; 20].into(), 1)
+		.nonce(2)
+		.fee_limit(300)
+		.sign(&signer)
+		.await
+		.unwrap();
+	submit_simulated_json_rpc(client.post(&url), stake).await.unwrap();
+	let called: Vec<_> = methods.try_iter().collect();
+	assert_eq!(called, ["l1x_submitTransactionV2"]);
+}
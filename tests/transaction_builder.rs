@@ -0,0 +1,137 @@
+use l1x_rpc::{
+	builder::TransactionBuilder,
+	rpc_model::{submit_transaction_request_v2::TransactionType, *},
+	signer::{LocalSigner, Signer},
+};
+use serde_json::{json, Value};
+use std::thread;
+
+const PRIVATE_KEY: &str = "6913aeae91daf9d6b5a8e3b4b4e9c0a6a2d0e5a51a9a3e1e8fbcad8c7e5c4a21";
+
+/// Minimal JSON-RPC node reporting account nonce 41 and a fee estimate of 250.
+fn spawn_node() -> String {
+	let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+	let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+
+	thread::spawn(move || {
+		for mut request in server.incoming_requests() {
+			let mut content = String::new();
+			request.as_reader().read_to_string(&mut content).unwrap();
+			let params: Value = serde_json::from_str(&content).unwrap();
+			let result = match params["method"].as_str().unwrap() {
+				"l1x_getAccountState" => json!({
+					"account_state": { "balance": "1000000", "nonce": "41", "account_type": 0 }
+				}),
+				"l1x_estimateFee" => {
+					assert!(params["params"]["request"]["transaction_type"]["Stake"].is_object());
+					json!({ "fee": "250" })
+				},
+				method => panic!("unexpected method {}", method),
+			};
+			let body = json!({ "jsonrpc": "2.0", "result": result, "id": 1 });
+			request.respond(tiny_http::Response::from_string(body.to_string())).unwrap();
+		}
+	});
+
+	url
+}
+
+#[tokio::test]
+async fn builder_matches_manual_assembly() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let request = TransactionBuilder::new()
+		.call([0x22; 20].into(), "transfer", b"{}".to_vec())
+		.deposit(5)
+		.nonce(7)
+		.fee_limit(100)
+		.sign(&signer)
+		.await
+		.unwrap();
+
+	let transaction_type =
+		TransactionType::SmartContractFunctionCall(SmartContractFunctionCallV2 {
+			contract_instance_address: vec![0x22; 20],
+			function_name: b"transfer".to_vec(),
+			arguments: b"{}".to_vec(),
+			deposit: "5".to_owned(),
+		});
+	let expected = SubmitTransactionRequestV2 {
+		nonce: "7".to_owned(),
+		transaction_type: Some(transaction_type.clone()),
+		fee_limit: "100".to_owned(),
+		signature: l1x_rpc::sign_v2(&signer, transaction_type, 100, 7).await.unwrap(),
+		verifying_key: signer.verifying_key().await.unwrap(),
+	};
+	assert_eq!(request, expected);
+	l1x_rpc::verify_v2(&request).unwrap();
+}
+
+#[tokio::test]
+async fn deployment_options() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let request = TransactionBuilder::new()
+		.deploy(vec![0xaa, 0xbb])
+		.salt(b"salt".to_vec())
+		.deposit(9)
+		.contract_type(l1x_rpc::transaction::ContractType::EVM)
+		.nonce(1)
+		.fee_limit(2)
+		.sign(&signer)
+		.await
+		.unwrap();
+
+	let Some(TransactionType::SmartContractDeployment(deployment)) = &request.transaction_type
+	else {
+		panic!("not a deployment")
+	};
+	assert_eq!(deployment.access_type, AccessType::Public as i32);
+	assert_eq!(deployment.contract_type, ContractType::Evm as i32);
+	assert_eq!(deployment.salt, b"salt");
+	assert_eq!(deployment.deposit, "9");
+	l1x_rpc::verify_v2(&request).unwrap();
+}
+
+#[tokio::test]
+async fn json_rpc_fills_nonce_and_fee() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let client = reqwest::Client::new().post(spawn_node());
+	let request = TransactionBuilder::new()
+		.stake([0x33; 20].into(), 1000)
+		.sign_with_json_rpc(&signer, client)
+		.await
+		.unwrap();
+
+	assert_eq!(request.nonce, "42");
+	assert_eq!(request.fee_limit, "250");
+	l1x_rpc::verify_v2(&request).unwrap();
+}
+
+#[tokio::test]
+async fn misuse_is_reported_on_sign() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let sign = |builder: TransactionBuilder| {
+		let signer = &signer;
+		async move { builder.nonce(1).fee_limit(1).sign(signer).await }
+	};
+
+	assert!(sign(TransactionBuilder::new()).await.is_err());
+	assert!(sign(TransactionBuilder::new().transfer([0x11; 20].into(), 1).salt(vec![1]))
+		.await
+		.is_err());
+	assert!(sign(TransactionBuilder::new().stake([0x11; 20].into(), 1).deposit(1))
+		.await
+		.is_err());
+	assert!(sign(
+		TransactionBuilder::new()
+			.transfer([0x11; 20].into(), 1)
+			.stake([0x11; 20].into(), 1)
+	)
+	.await
+	.is_err());
+	assert!(TransactionBuilder::new()
+		.transfer([0x11; 20].into(), 1)
+		.nonce(1)
+		.sign(&signer)
+		.await
+		.is_err());
+}
@@ -0,0 +1,423 @@
+use l1x_rpc::{
+	address::AddressError,
+	rpc_model::{self, transaction_v3::Transaction, *},
+	transaction::{self, TransactionTypeV2, TransactionVersion},
+};
+
+fn proto_v3(transaction: Transaction) -> rpc_model::TransactionV3 {
+	rpc_model::TransactionV3 {
+		version: rpc_model::TransactionVersion::V3 as i32,
+		tx_type: transaction.tx_type() as i32,
+		transaction: Some(transaction),
+		nonce: "7".to_owned(),
+		fee_limit: "100".to_owned(),
+		signature: vec![0xaa; 64],
+		verifying_key: vec![0x02; 33],
+		eth_original_transaction: None,
+	}
+}
+
+fn response(transaction: rpc_model::TransactionV3) -> rpc_model::TransactionV3Response {
+	rpc_model::TransactionV3Response {
+		transaction: Some(transaction),
+		from: vec![0x44; 20],
+		transaction_hash: vec![0x55; 32],
+		block_hash: vec![0x66; 32],
+		block_number: 12,
+		fee_used: "3".to_owned(),
+		timestamp: 1_700_000_000,
+	}
+}
+
+fn convert(transaction: Transaction) -> Result<TransactionTypeV2, ConversionError> {
+	transaction::TransactionV3::try_from(proto_v3(transaction)).map(|t| t.transaction_type)
+}
+
+#[test]
+fn every_transaction_variant() {
+	let cases = [
+		(
+			Transaction::NativeTokenTransfer(NativeTokenTransfer {
+				address: vec![0x11; 20],
+				amount: "1000".to_owned(),
+			}),
+			TransactionTypeV2::NativeTokenTransfer([0x11; 20], 1000),
+		),
+		(
+			Transaction::SmartContractDeployment(SmartContractDeploymentV2 {
+				access_type: rpc_model::AccessType::Resticted as i32,
+				contract_type: rpc_model::ContractType::Evm as i32,
+				contract_code: vec![1, 2, 3],
+				deposit: "5".to_owned(),
+				salt: b"salt".to_vec(),
+			}),
+			TransactionTypeV2::SmartContractDeployment {
+				access_type: transaction::AccessType::RESTICTED,
+				contract_type: transaction::ContractType::EVM,
+				contract_code: vec![1, 2, 3],
+				deposit: 5,
+				salt: b"salt".to_vec(),
+			},
+		),
+		(
+			Transaction::SmartContractInit(SmartContractInitV2 {
+				contract_code_address: vec![0x22; 20],
+				arguments: b"{}".to_vec(),
+				deposit: "6".to_owned(),
+			}),
+			TransactionTypeV2::SmartContractInit {
+				contract_code_address: [0x22; 20],
+				arguments: b"{}".to_vec(),
+				deposit: 6,
+			},
+		),
+		(
+			Transaction::SmartContractFunctionCall(SmartContractFunctionCallV2 {
+				contract_instance_address: vec![0x33; 20],
+				function_name: b"get".to_vec(),
+				arguments: b"[]".to_vec(),
+				deposit: "0".to_owned(),
+			}),
+			TransactionTypeV2::SmartContractFunctionCall {
+				contract_instance_address: [0x33; 20],
+				function: b"get".to_vec(),
+				arguments: b"[]".to_vec(),
+				deposit: 0,
+			},
+		),
+		(
+			Transaction::Stake(Stake { pool_address: vec![0x44; 20], amount: "7".to_owned() }),
+			TransactionTypeV2::Stake { pool_address: [0x44; 20], amount: 7 },
+		),
+		(
+			Transaction::Unstake(UnStake { pool_address: vec![0x44; 20], amount: "8".to_owned() }),
+			TransactionTypeV2::UnStake { pool_address: [0x44; 20], amount: 8 },
+		),
+	];
+	for (proto, expected) in cases {
+		assert_eq!(convert(proto.clone()).unwrap(), expected);
+		assert_eq!(TransactionTypeV2::try_from(proto).unwrap(), expected);
+	}
+}
+
+#[test]
+fn versions_are_shifted_by_one() {
+	let transfer = Transaction::Stake(Stake { pool_address: vec![0; 20], amount: "1".to_owned() });
+	for (proto, domain) in [
+		(rpc_model::TransactionVersion::V1, TransactionVersion::V1),
+		(rpc_model::TransactionVersion::V2, TransactionVersion::V2),
+		(rpc_model::TransactionVersion::V3, TransactionVersion::V3),
+	] {
+		let message =
+			rpc_model::TransactionV3 { version: proto as i32, ..proto_v3(transfer.clone()) };
+		assert_eq!(transaction::TransactionV3::try_from(message).unwrap().version, domain);
+	}
+
+	let message = rpc_model::TransactionV3 { version: 3, ..proto_v3(transfer) };
+	assert_eq!(
+		transaction::TransactionV3::try_from(message),
+		Err(ConversionError::InvalidEnum { field: "version", value: 3 })
+	);
+}
+
+#[test]
+fn response_fields() {
+	let eth_tx = Some(vec![0xf8, 0x6c]);
+	let message = rpc_model::TransactionV3 {
+		eth_original_transaction: eth_tx.clone(),
+		..proto_v3(Transaction::Stake(Stake { pool_address: vec![1; 20], amount: "2".to_owned() }))
+	};
+	let converted = transaction::TransactionV3Response::try_from(response(message)).unwrap();
+	assert_eq!(
+		converted,
+		transaction::TransactionV3Response {
+			transaction: transaction::TransactionV3 {
+				version: TransactionVersion::V3,
+				nonce: 7,
+				transaction_type: TransactionTypeV2::Stake { pool_address: [1; 20], amount: 2 },
+				fee_limit: 100,
+				signature: vec![0xaa; 64],
+				verifying_key: vec![0x02; 33],
+				eth_original_transaction: eth_tx,
+			},
+			from: [0x44; 20],
+			transaction_hash: [0x55; 32],
+			block_hash: [0x66; 32],
+			block_number: 12,
+			fee_used: 3,
+			timestamp: 1_700_000_000,
+		}
+	);
+}
+
+#[test]
+fn invalid_fields_are_named() {
+	let stake = || Transaction::Stake(Stake { pool_address: vec![1; 20], amount: "2".to_owned() });
+
+	assert_eq!(
+		convert(Transaction::Stake(Stake { pool_address: vec![1; 20], amount: "-2".to_owned() })),
+		Err(ConversionError::InvalidNumber { field: "stake.amount", value: "-2".to_owned() })
+	);
+	assert_eq!(
+		convert(Transaction::Unstake(UnStake {
+			pool_address: vec![1; 19],
+			amount: "2".to_owned()
+		})),
+		Err(ConversionError::Address(AddressError::InvalidLength {
+			field: "unstake.pool_address",
+			len: 19
+		}))
+	);
+	assert_eq!(
+		convert(Transaction::SmartContractDeployment(SmartContractDeploymentV2 {
+			access_type: 9,
+			..Default::default()
+		})),
+		Err(ConversionError::InvalidEnum {
+			field: "smart_contract_deployment.access_type",
+			value: 9
+		})
+	);
+
+	let mismatched = rpc_model::TransactionV3 {
+		tx_type: rpc_model::TransactionType::Unstake as i32,
+		..proto_v3(stake())
+	};
+	assert!(matches!(
+		transaction::TransactionV3::try_from(mismatched),
+		Err(ConversionError::TxTypeMismatch { tx_type: rpc_model::TransactionType::Unstake, .. })
+	));
+	let unspecified = rpc_model::TransactionV3 {
+		tx_type: rpc_model::TransactionType::Unspecified as i32,
+		..proto_v3(stake())
+	};
+	assert!(transaction::TransactionV3::try_from(unspecified).is_ok());
+
+	let missing = rpc_model::TransactionV3 { transaction: None, ..proto_v3(stake()) };
+	assert_eq!(
+		transaction::TransactionV3::try_from(missing),
+		Err(ConversionError::MissingField("transaction"))
+	);
+
+	let short_hash =
+		rpc_model::TransactionV3Response { block_hash: vec![0; 31], ..response(proto_v3(stake())) };
+	assert_eq!(
+		transaction::TransactionV3Response::try_from(short_hash),
+		Err(ConversionError::InvalidHashLength { field: "block_hash", len: 31 })
+	);
+	let negative_block =
+		rpc_model::TransactionV3Response { block_number: -1, ..response(proto_v3(stake())) };
+	assert_eq!(
+		transaction::TransactionV3Response::try_from(negative_block),
+		Err(ConversionError::InvalidNumber { field: "block_number", value: "-1".to_owned() })
+	);
+}
+
+/// SplitMix64, so the round-trip cases are reproducible without a property testing crate.
+struct Rng(u64);
+
+impl Rng {
+	fn next(&mut self) -> u64 {
+		self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+		let mut z = self.0;
+		z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+		z ^ (z >> 31)
+	}
+
+	fn balance(&mut self) -> u128 {
+		match self.next() % 4 {
+			0 => 0,
+			1 => u128::MAX,
+			_ => (self.next() as u128) << (self.next() % 64) | self.next() as u128,
+		}
+	}
+
+	fn address(&mut self) -> [u8; 20] {
+		let mut address = [0u8; 20];
+		address.iter_mut().for_each(|b| *b = self.next() as u8);
+		address
+	}
+
+	fn bytes(&mut self) -> Vec<u8> {
+		(0..self.next() % 48).map(|_| self.next() as u8).collect()
+	}
+
+	fn option<T>(&mut self, value: impl FnOnce(&mut Self) -> T) -> Option<T> {
+		self.next().is_multiple_of(2).then(|| value(self))
+	}
+
+	fn access_type(&mut self) -> transaction::AccessType {
+		[
+			transaction::AccessType::PRIVATE,
+			transaction::AccessType::PUBLIC,
+			transaction::AccessType::RESTICTED,
+		][self.next() as usize % 3]
+			.clone()
+	}
+
+	fn contract_type(&mut self) -> transaction::ContractType {
+		[
+			transaction::ContractType::L1XVM,
+			transaction::ContractType::EVM,
+			transaction::ContractType::XTALK,
+		][self.next() as usize % 3]
+			.clone()
+	}
+
+	/// A random transaction of every variant.
+	fn transactions(&mut self) -> Vec<TransactionTypeV2> {
+		vec![
+			TransactionTypeV2::NativeTokenTransfer(self.address(), self.balance()),
+			TransactionTypeV2::SmartContractDeployment {
+				access_type: self.access_type(),
+				contract_type: self.contract_type(),
+				contract_code: self.bytes(),
+				deposit: self.balance(),
+				salt: self.bytes(),
+			},
+			TransactionTypeV2::SmartContractInit {
+				contract_code_address: self.address(),
+				arguments: self.bytes(),
+				deposit: self.balance(),
+			},
+			TransactionTypeV2::SmartContractFunctionCall {
+				contract_instance_address: self.address(),
+				function: self.bytes(),
+				arguments: self.bytes(),
+				deposit: self.balance(),
+			},
+			TransactionTypeV2::CreateStakingPool {
+				contract_instance_address: self.option(Rng::address),
+				min_stake: self.option(Rng::balance),
+				max_stake: self.option(Rng::balance),
+				min_pool_balance: self.option(Rng::balance),
+				max_pool_balance: self.option(Rng::balance),
+				staking_period: self.option(Rng::balance),
+			},
+			TransactionTypeV2::Stake { pool_address: self.address(), amount: self.balance() },
+			TransactionTypeV2::UnStake { pool_address: self.address(), amount: self.balance() },
+			TransactionTypeV2::StakingPoolContract {
+				pool_address: self.address(),
+				contract_instance_address: self.address(),
+			},
+		]
+	}
+}
+
+#[test]
+fn domain_transactions_round_trip() {
+	let mut rng = Rng(0x1f1f);
+	for _ in 0..256 {
+		for domain in rng.transactions() {
+			let staking_pool_contract =
+				matches!(domain, TransactionTypeV2::StakingPoolContract { .. });
+			let create_staking_pool = matches!(domain, TransactionTypeV2::CreateStakingPool { .. });
+
+			let payload = l1x_rpc::TransactionTypeV2SignPayload::from(domain.clone());
+			assert_eq!(TransactionTypeV2::try_from(payload).unwrap(), domain);
+
+			let submit = submit_transaction_request_v2::TransactionType::try_from(domain.clone());
+			let estimate = estimate_fee_request::TransactionType::try_from(domain.clone());
+			if staking_pool_contract {
+				assert!(matches!(submit, Err(ConversionError::Unrepresentable { .. })));
+				assert!(matches!(estimate, Err(ConversionError::Unrepresentable { .. })));
+				continue
+			}
+			assert_eq!(TransactionTypeV2::try_from(submit.unwrap()).unwrap(), domain);
+			assert_eq!(TransactionTypeV2::try_from(estimate.unwrap()).unwrap(), domain);
+
+			let transaction = transaction::TransactionV3 {
+				version: TransactionVersion::V3,
+				nonce: rng.balance(),
+				transaction_type: domain,
+				fee_limit: rng.balance(),
+				signature: rng.bytes(),
+				verifying_key: rng.bytes(),
+				eth_original_transaction: rng.option(Rng::bytes),
+			};
+			let proto = rpc_model::TransactionV3::try_from(transaction.clone());
+			if create_staking_pool {
+				assert_eq!(
+					proto,
+					Err(ConversionError::Unrepresentable {
+						transaction: "CreateStakingPool",
+						message: "TransactionV3"
+					})
+				);
+				continue
+			}
+			assert_eq!(transaction::TransactionV3::try_from(proto.unwrap()).unwrap(), transaction);
+		}
+	}
+}
+
+#[test]
+fn versions_round_trip() {
+	for version in [TransactionVersion::V1, TransactionVersion::V2, TransactionVersion::V3] {
+		let proto = rpc_model::TransactionVersion::from(version.clone());
+		assert_eq!(TransactionVersion::from(proto), version);
+	}
+}
+
+fn pool(min_stake: &str) -> CreateStakingPool {
+	CreateStakingPool {
+		contract_instance_address: None,
+		min_stake: Some(min_stake.to_owned()),
+		max_stake: Some("1000".to_owned()),
+		min_pool_balance: None,
+		max_pool_balance: None,
+		staking_period: Some("10".to_owned()),
+	}
+}
+
+#[test]
+fn staking_pool_limits_are_parsed_strictly() {
+	let expected = ConversionError::InvalidNumber {
+		field: "create_staking_pool.min_stake",
+		value: "10O".to_owned(),
+	};
+
+	let submit = submit_transaction_request_v2::TransactionType::CreateStakingPool(pool("10O"));
+	let error = TransactionTypeV2::try_from(submit).unwrap_err();
+	assert_eq!(error.downcast::<ConversionError>().unwrap(), expected);
+
+	let estimate = estimate_fee_request::TransactionType::CreateStakingPool(pool("10O"));
+	let error = TransactionTypeV2::try_from(estimate).unwrap_err();
+	assert_eq!(error.downcast::<ConversionError>().unwrap(), expected);
+
+	let submit_v1 = submit_transaction_request::TransactionType::CreateStakingPool(pool("10O"));
+	let error = transaction::TransactionType::try_from(submit_v1).unwrap_err();
+	assert_eq!(error.downcast::<ConversionError>().unwrap(), expected);
+
+	let negative = CreateStakingPool { staking_period: Some("-1".to_owned()), ..pool("1") };
+	let submit = submit_transaction_request_v2::TransactionType::CreateStakingPool(negative);
+	let error = TransactionTypeV2::try_from(submit).unwrap_err();
+	assert_eq!(
+		error.downcast::<ConversionError>().unwrap(),
+		ConversionError::InvalidNumber {
+			field: "create_staking_pool.staking_period",
+			value: "-1".to_owned()
+		}
+	);
+}
+
+#[test]
+fn staking_pool_in_v1_request() {
+	let submit_v1 =
+		submit_transaction_request::TransactionType::CreateStakingPool(CreateStakingPool {
+			contract_instance_address: Some(vec![0x77; 20]),
+			..pool("5")
+		});
+	assert_eq!(
+		transaction::TransactionType::try_from(submit_v1).unwrap(),
+		transaction::TransactionType::CreateStakingPool {
+			contract_instance_address: Some([0x77; 20]),
+			min_stake: Some(5),
+			max_stake: Some(1000),
+			min_pool_balance: None,
+			max_pool_balance: None,
+			staking_period: Some(10),
+		}
+	);
+}
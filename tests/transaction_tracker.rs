@@ -0,0 +1,169 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use l1x_rpc::{
+	primitives::BlockNumber,
+	rpc_model::{self, transaction_v3::Transaction, *},
+	tracker::{ChainSource, Outcome, TrackerConfig, TransactionTracker},
+};
+use std::{
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+
+#[derive(Default)]
+struct State {
+	/// Receipt polls answered with "not included" before the receipt is returned.
+	pending_polls: usize,
+	receipt: Option<GetTransactionV3ReceiptResponse>,
+	receipt_polls: usize,
+	/// Head block number, advanced by one on every read.
+	head: BlockNumber,
+	/// Head block number after which the chain stops producing blocks.
+	stall_at: BlockNumber,
+}
+
+#[derive(Clone, Default)]
+struct Chain(Arc<Mutex<State>>);
+
+#[async_trait]
+impl ChainSource for Chain {
+	async fn receipt(&self, hash: &str) -> Result<Option<GetTransactionV3ReceiptResponse>> {
+		assert_eq!(hash, "abcd");
+		let mut state = self.0.lock().unwrap();
+		state.receipt_polls += 1;
+		if state.receipt_polls <= state.pending_polls {
+			return Ok(None)
+		}
+		Ok(state.receipt.clone())
+	}
+
+	async fn head_block_number(&self) -> Result<BlockNumber> {
+		let mut state = self.0.lock().unwrap();
+		if state.head < state.stall_at {
+			state.head += 1;
+		}
+		Ok(state.head)
+	}
+}
+
+fn receipt(status: TransactionStatus) -> GetTransactionV3ReceiptResponse {
+	let transaction = Transaction::SmartContractDeployment(SmartContractDeploymentV2 {
+		access_type: AccessType::Public as i32,
+		contract_type: ContractType::L1xvm as i32,
+		contract_code: vec![1, 2, 3],
+		deposit: "0".to_owned(),
+		salt: vec![],
+	});
+	GetTransactionV3ReceiptResponse {
+		transaction: Some(rpc_model::TransactionV3Response {
+			transaction: Some(rpc_model::TransactionV3 {
+				version: rpc_model::TransactionVersion::V3 as i32,
+				tx_type: transaction.tx_type() as i32,
+				transaction: Some(transaction),
+				nonce: "7".to_owned(),
+				fee_limit: "100".to_owned(),
+				signature: vec![0xaa; 64],
+				verifying_key: vec![0x02; 33],
+				eth_original_transaction: None,
+			}),
+			from: vec![0x44; 20],
+			transaction_hash: vec![0xab; 32],
+			block_hash: vec![0x66; 32],
+			block_number: 10,
+			fee_used: "42".to_owned(),
+			timestamp: 1_700_000_000,
+		}),
+		status: status as i32,
+	}
+}
+
+fn submitted() -> SubmitTransactionResponse {
+	SubmitTransactionResponse { hash: "abcd".to_owned(), contract_address: Some("c0de".to_owned()) }
+}
+
+fn config(confirmations: u64) -> TrackerConfig {
+	TrackerConfig {
+		confirmations,
+		initial_backoff: Duration::from_millis(1),
+		max_backoff: Duration::from_millis(4),
+		inclusion_timeout: Duration::from_millis(200),
+		confirmation_timeout: Duration::from_millis(200),
+	}
+}
+
+#[tokio::test]
+async fn confirmed_after_inclusion_and_blocks() {
+	let chain = Chain::default();
+	*chain.0.lock().unwrap() = State {
+		pending_polls: 3,
+		receipt: Some(receipt(TransactionStatus::Succeed)),
+		head: 9,
+		stall_at: BlockNumber::MAX,
+		..State::default()
+	};
+	let tracker = TransactionTracker::new(chain.clone(), config(3));
+
+	let Outcome::Confirmed(receipt) = tracker.track(submitted()).await.unwrap() else {
+		panic!("not confirmed")
+	};
+	assert_eq!(receipt.status, TransactionStatus::Succeed);
+	assert_eq!((receipt.block_number, receipt.fee_used), (10, 42));
+	assert_eq!(receipt.contract_address.as_deref(), Some("c0de"));
+	let state = chain.0.lock().unwrap();
+	assert_eq!(state.receipt_polls, 4);
+	assert_eq!(state.head, 13);
+}
+
+#[tokio::test]
+async fn failed_is_reported_on_inclusion() {
+	let chain = Chain::default();
+	*chain.0.lock().unwrap() =
+		State { receipt: Some(receipt(TransactionStatus::Failed)), ..State::default() };
+	let tracker = TransactionTracker::new(chain, config(100));
+
+	let Outcome::Failed(receipt) = tracker.track(submitted()).await.unwrap() else {
+		panic!("not failed")
+	};
+	assert_eq!(receipt.status, TransactionStatus::Failed);
+}
+
+#[tokio::test]
+async fn dropped_when_never_included() {
+	let tracker = TransactionTracker::new(Chain::default(), config(1));
+	assert_eq!(
+		tracker.track(submitted()).await.unwrap(),
+		Outcome::Dropped { hash: "abcd".to_owned() }
+	);
+}
+
+#[tokio::test]
+async fn unconfirmed_after_confirmation_timeout() {
+	let chain = Chain::default();
+	*chain.0.lock().unwrap() = State {
+		receipt: Some(receipt(TransactionStatus::Succeed)),
+		head: 10,
+		stall_at: 12,
+		..State::default()
+	};
+	let tracker = TransactionTracker::new(chain, config(5));
+
+	let Outcome::Unconfirmed { receipt, confirmations, required } =
+		tracker.track(submitted()).await.unwrap()
+	else {
+		panic!("not unconfirmed")
+	};
+	assert_eq!((confirmations, required), (2, 5));
+	assert_eq!(receipt.block_number, 10);
+}
+
+#[tokio::test]
+async fn undecodable_receipt_is_an_error() {
+	let chain = Chain::default();
+	let receipt =
+		GetTransactionV3ReceiptResponse { status: 7, ..receipt(TransactionStatus::Succeed) };
+	*chain.0.lock().unwrap() = State { receipt: Some(receipt), ..State::default() };
+	let tracker = TransactionTracker::new(chain, config(1));
+
+	let error = tracker.track(submitted()).await.unwrap_err();
+	assert_eq!(error.to_string(), "Invalid transaction status 7");
+}
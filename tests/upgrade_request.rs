@@ -0,0 +1,113 @@
+use l1x_rpc::{
+	rpc_model::{submit_transaction_request::TransactionType, *},
+	signer::{LocalSigner, Signer},
+	upgrade::{upgrade_request, UpgradeChange},
+	VerifyError,
+};
+
+const PRIVATE_KEY: &str = "6913aeae91daf9d6b5a8e3b4b4e9c0a6a2d0e5a51a9a3e1e8fbcad8c7e5c4a21";
+
+async fn signed_v1(
+	signer: &LocalSigner,
+	transaction_type: TransactionType,
+) -> SubmitTransactionRequest {
+	let signature = l1x_rpc::sign(signer, transaction_type.clone(), 100, 7).await.unwrap();
+	let request = SubmitTransactionRequest {
+		nonce: "7".to_owned(),
+		transaction_type: Some(transaction_type),
+		fee_limit: "100".to_owned(),
+		signature,
+		verifying_key: signer.verifying_key().await.unwrap(),
+	};
+	l1x_rpc::verify(&request).unwrap();
+	request
+}
+
+fn deployment(value: u64) -> TransactionType {
+	TransactionType::SmartContractDeployment(SmartContractDeployment {
+		access_type: AccessType::Public as i32,
+		contract_type: ContractType::L1xvm as i32,
+		contract_code: vec![0xaa; 8],
+		value,
+		salt: b"salt".to_vec(),
+	})
+}
+
+#[tokio::test]
+async fn upgrade_resigns_under_v2_payload() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let v1 = signed_v1(&signer, deployment(5)).await;
+
+	let upgraded = upgrade_request(v1, &signer).await.unwrap();
+	l1x_rpc::verify_v2(&upgraded.request).unwrap();
+	assert_eq!(upgraded.changes, vec![UpgradeChange::ValueAsDeposit { value: 5 }]);
+	let Some(submit_transaction_request_v2::TransactionType::SmartContractDeployment(deployment)) =
+		upgraded.request.transaction_type
+	else {
+		panic!("not a deployment")
+	};
+	assert_eq!(deployment.deposit, "5");
+	assert_eq!(deployment.salt, b"salt");
+}
+
+#[tokio::test]
+async fn upgrade_without_changes() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	for transaction_type in [
+		deployment(0),
+		TransactionType::NativeTokenTransfer(NativeTokenTransfer {
+			address: vec![0x11; 20],
+			amount: "1000".to_owned(),
+		}),
+		TransactionType::SmartContractFunctionCall(SmartContractFunctionCall {
+			contract_address: vec![0x22; 20],
+			function_name: b"get".to_vec(),
+			arguments: b"{}".to_vec(),
+		}),
+	] {
+		let upgraded = upgrade_request(signed_v1(&signer, transaction_type).await, &signer)
+			.await
+			.unwrap();
+		assert!(upgraded.changes.is_empty());
+		assert_eq!(
+			(upgraded.request.nonce.as_str(), upgraded.request.fee_limit.as_str()),
+			("7", "100")
+		);
+		l1x_rpc::verify_v2(&upgraded.request).unwrap();
+	}
+}
+
+#[tokio::test]
+async fn upgrade_rejects_other_signer_and_invalid_requests() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let other = LocalSigner::from_hex(&"11".repeat(32)).unwrap();
+	let v1 = signed_v1(&signer, deployment(0)).await;
+	assert!(upgrade_request(v1.clone(), &other).await.is_err());
+
+	let bad_address = SubmitTransactionRequest {
+		transaction_type: Some(TransactionType::Stake(Stake {
+			pool_address: vec![0x11; 19],
+			amount: "1".to_owned(),
+		})),
+		..v1.clone()
+	};
+	assert!(upgrade_request(bad_address, &signer).await.is_err());
+
+	let bad_nonce = SubmitTransactionRequest { nonce: "seven".to_owned(), ..v1 };
+	assert!(upgrade_request(bad_nonce, &signer).await.is_err());
+}
+
+#[tokio::test]
+async fn upgrade_requires_a_valid_v1_signature() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let v1 = signed_v1(&signer, deployment(0)).await;
+
+	// A request altered after signing is not re-signed as if the sender had approved it.
+	let altered = SubmitTransactionRequest { fee_limit: "1000".to_owned(), ..v1.clone() };
+	let error = upgrade_request(altered, &signer).await.unwrap_err();
+	assert!(matches!(error.downcast_ref::<VerifyError>(), Some(VerifyError::SignatureMismatch)));
+
+	let unsigned = SubmitTransactionRequest { signature: Vec::new(), ..v1 };
+	let error = upgrade_request(unsigned, &signer).await.unwrap_err();
+	assert!(matches!(error.downcast_ref::<VerifyError>(), Some(VerifyError::InvalidSignature(_))));
+}
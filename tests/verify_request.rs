@@ -0,0 +1,78 @@
+use l1x_rpc::{
+	rpc_model::{submit_transaction_request::TransactionType, *},
+	signer::{LocalSigner, Signer},
+	VerifyError,
+};
+
+const PRIVATE_KEY: &str = "6913aeae91daf9d6b5a8e3b4b4e9c0a6a2d0e5a51a9a3e1e8fbcad8c7e5c4a21";
+
+fn transfer() -> TransactionType {
+	TransactionType::NativeTokenTransfer(NativeTokenTransfer {
+		address: vec![0x11; 20],
+		amount: "1000".to_owned(),
+	})
+}
+
+async fn signed_v1(signer: &LocalSigner) -> SubmitTransactionRequest {
+	SubmitTransactionRequest {
+		nonce: "7".to_owned(),
+		transaction_type: Some(transfer()),
+		fee_limit: "100".to_owned(),
+		signature: l1x_rpc::sign(signer, transfer(), 100, 7).await.unwrap(),
+		verifying_key: signer.verifying_key().await.unwrap(),
+	}
+}
+
+async fn signed_v2(signer: &LocalSigner) -> SubmitTransactionRequestV2 {
+	let transfer = submit_transaction_request_v2::TransactionType::from(transfer());
+	SubmitTransactionRequestV2 {
+		nonce: "7".to_owned(),
+		transaction_type: Some(transfer.clone()),
+		fee_limit: "100".to_owned(),
+		signature: l1x_rpc::sign_v2(signer, transfer, 100, 7).await.unwrap(),
+		verifying_key: signer.verifying_key().await.unwrap(),
+	}
+}
+
+#[tokio::test]
+async fn v1() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let other_key = LocalSigner::from_hex(&"11".repeat(32)).unwrap().verifying_key().await.unwrap();
+	let request = signed_v1(&signer).await;
+	l1x_rpc::verify(&request).unwrap();
+	assert_eq!(l1x_rpc::sign_payload(&request).unwrap().fee_limit, 100);
+
+	let tampered = SubmitTransactionRequest { fee_limit: "101".to_owned(), ..request.clone() };
+	assert!(matches!(l1x_rpc::verify(&tampered), Err(VerifyError::SignatureMismatch)));
+
+	let wrong_key = SubmitTransactionRequest { verifying_key: other_key, ..request.clone() };
+	assert!(matches!(l1x_rpc::verify(&wrong_key), Err(VerifyError::SignatureMismatch)));
+
+	let malformed_key = SubmitTransactionRequest { verifying_key: vec![0; 33], ..request.clone() };
+	assert!(matches!(l1x_rpc::verify(&malformed_key), Err(VerifyError::InvalidVerifyingKey(_))));
+
+	let missing_type = SubmitTransactionRequest { transaction_type: None, ..request };
+	assert!(matches!(l1x_rpc::verify(&missing_type), Err(VerifyError::Payload(_))));
+}
+
+#[tokio::test]
+async fn v2() {
+	let signer = LocalSigner::from_hex(PRIVATE_KEY).unwrap();
+	let other_key = LocalSigner::from_hex(&"11".repeat(32)).unwrap().verifying_key().await.unwrap();
+	let request = signed_v2(&signer).await;
+	l1x_rpc::verify_v2(&request).unwrap();
+	assert_eq!(l1x_rpc::sign_payload_v2(&request).unwrap().nonce, "7");
+
+	let tampered = SubmitTransactionRequestV2 { nonce: "8".to_owned(), ..request.clone() };
+	assert!(matches!(l1x_rpc::verify_v2(&tampered), Err(VerifyError::SignatureMismatch)));
+
+	let wrong_key = SubmitTransactionRequestV2 { verifying_key: other_key, ..request.clone() };
+	assert!(matches!(l1x_rpc::verify_v2(&wrong_key), Err(VerifyError::SignatureMismatch)));
+
+	let malformed_key =
+		SubmitTransactionRequestV2 { verifying_key: vec![0; 33], ..request.clone() };
+	assert!(matches!(l1x_rpc::verify_v2(&malformed_key), Err(VerifyError::InvalidVerifyingKey(_))));
+
+	let missing_type = SubmitTransactionRequestV2 { transaction_type: None, ..request };
+	assert!(matches!(l1x_rpc::verify_v2(&missing_type), Err(VerifyError::Payload(_))));
+}
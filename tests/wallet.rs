@@ -0,0 +1,93 @@
+use l1x_rpc::wallet::{ChildNumber, DerivationPath, ExtendedPrivateKey, HdWallet};
+
+/// Checks `key` against the chain code, private key and depth of the BIP-32 serialized `xprv`.
+fn assert_xprv(key: &ExtendedPrivateKey, xprv: &str) {
+	let bytes = bs58::decode(xprv).with_check(None).into_vec().unwrap();
+	assert_eq!(bytes.len(), 78);
+	assert_eq!(key.depth, bytes[4], "{}", xprv);
+	assert_eq!(key.chain_code[..], bytes[13..45], "{}", xprv);
+	assert_eq!(bytes[45], 0);
+	assert_eq!(key.secret_key.secret_bytes()[..], bytes[46..], "{}", xprv);
+}
+
+fn assert_vector(seed: &str, vector: &[(&str, &str)]) {
+	let master = ExtendedPrivateKey::new_master(&hex::decode(seed).unwrap()).unwrap();
+	for (path, xprv) in vector {
+		let path: DerivationPath = path.parse().unwrap();
+		assert_xprv(&master.derive_path(&path).unwrap(), xprv);
+	}
+}
+
+#[test]
+fn bip32_test_vector_1() {
+	assert_vector(
+		"000102030405060708090a0b0c0d0e0f",
+		&[
+			("m", "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi"),
+			("m/0'", "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7"),
+			("m/0'/1", "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs"),
+			("m/0'/1/2'", "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM"),
+			("m/0'/1/2'/2", "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334"),
+			("m/0'/1/2'/2/1000000000", "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76"),
+		],
+	);
+}
+
+#[test]
+fn bip32_test_vector_2() {
+	assert_vector(
+		"fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+		&[
+			("m", "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U"),
+			("m/0", "xprv9vHkqa6EV4sPZHYqZznhT2NPtPCjKuDKGY38FBWLvgaDx45zo9WQRUT3dKYnjwih2yJD9mkrocEZXo1ex8G81dwSM1fwqWpWkeS3v86pgKt"),
+			("m/0/2147483647'", "xprv9wSp6B7kry3Vj9m1zSnLvN3xH8RdsPP1Mh7fAaR7aRLcQMKTR2vidYEeEg2mUCTAwCd6vnxVrcjfy2kRgVsFawNzmjuHc2YmYRmagcEPdU9"),
+			("m/0/2147483647'/1", "xprv9zFnWC6h2cLgpmSA46vutJzBcfJ8yaJGg8cX1e5StJh45BBciYTRXSd25UEPVuesF9yog62tGAQtHjXajPPdbRCHuWS6T8XA2ECKADdw4Ef"),
+			("m/0/2147483647'/1/2147483646'", "xprvA1RpRA33e1JQ7ifknakTFpgNXPmW2YvmhqLQYMmrj4xJXXWYpDPS3xz7iAxn8L39njGVyuoseXzU6rcxFLJ8HFsTjSyQbLYnMpCqE2VbFWc"),
+			("m/0/2147483647'/1/2147483646'/2", "xprvA2nrNbFZABcdryreWet9Ea4LvTJcGsqrMzxHx98MMrotbir7yrKCEXw7nadnHM8Dq38EGfSh6dqA9QWTyefMLEcBYJUuekgW4BYPJcr9E7j"),
+		],
+	);
+}
+
+const PHRASE: &str =
+	"abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+#[test]
+fn bip39_seed_with_passphrase() {
+	let mnemonic = l1x_rpc::wallet::validate_mnemonic(PHRASE).unwrap();
+	assert_eq!(
+		hex::encode(mnemonic.to_seed("TREZOR")),
+		"c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+	);
+	assert_xprv(
+		&ExtendedPrivateKey::new_master(&mnemonic.to_seed("TREZOR")).unwrap(),
+		"xprv9s21ZrQH143K3h3fDYiay8mocZ3afhfULfb5GX8kCBdno77K4HiA15Tg23wpbeF1pLfs1c5SPmYHrEpTuuRhxMwvKDwqdKiGJS9XFKzUsAF",
+	);
+}
+
+#[test]
+fn default_account_address() {
+	// The first address MetaMask shows for this mnemonic.
+	let wallet = HdWallet::from_phrase(PHRASE, "").unwrap();
+	let addresses = wallet.addresses(0..1).unwrap();
+	assert_eq!(addresses[0].path.to_string(), "m/44'/60'/0'/0/0");
+	assert_eq!(addresses[0].address, "9858effd232b4033e47d90003d41ec34ecaeda94");
+	assert_eq!(
+		l1x_rpc::get_address_from_private_key(&wallet.account(0).unwrap()).unwrap(),
+		addresses[0].address
+	);
+}
+
+#[test]
+fn hardened_indexes_are_rejected() {
+	let wallet = HdWallet::from_phrase(PHRASE, "").unwrap();
+	assert!(wallet.account(0x8000_0000).is_err());
+	assert!(wallet.addresses(0x7fff_ffff..0x8000_0001).is_err());
+	assert!(ChildNumber::normal(0x8000_0000).is_err());
+	assert!(ChildNumber::hardened(u32::MAX).is_err());
+	assert!("m/2147483648".parse::<DerivationPath>().is_err());
+	assert!("m/2147483648'".parse::<DerivationPath>().is_err());
+
+	let master = ExtendedPrivateKey::new_master(&[0; 16]).unwrap();
+	assert!(master.derive_child(ChildNumber::Normal(0x8000_0000)).is_err());
+	assert_eq!(ChildNumber::normal(0x7fff_ffff).unwrap(), ChildNumber::Normal(0x7fff_ffff));
+}